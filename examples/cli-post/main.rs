@@ -1,33 +1,37 @@
 // TODO: handle errors instead of panics (avoid .unwrap())
 
+// diesel 1.x derives and `table!` emit impls inside functions
+#![allow(non_local_definitions)]
+
+// The example is written for diesel 1. With the `diesel2` or `serde` feature the generated code needs diesel 2 or
+// serde, so with them, e.g. with `--all-features`, it only tells how to run it.
+#[cfg(not(any(feature = "diesel2", feature = "serde")))]
 #[macro_use]
 extern crate diesel;
 
-use clap::Parser;
-use cli::*;
-use colored::*;
-use diesel::prelude::*;
-use models::*;
+#[cfg(not(any(feature = "diesel2", feature = "serde")))]
+use {clap::Parser, cli::*, colored::*, diesel::prelude::*, models::*};
 
+#[cfg(not(any(feature = "diesel2", feature = "serde")))]
 mod cli;
+#[cfg(not(any(feature = "diesel2", feature = "serde")))]
 mod models;
+#[cfg(not(any(feature = "diesel2", feature = "serde")))]
 mod schema;
 
+#[cfg(any(feature = "diesel2", feature = "serde"))]
+fn main() {
+    eprintln!("cli-post is written for diesel 1, run it with the default features of diesel-ease");
+}
+
+#[cfg(not(any(feature = "diesel2", feature = "serde")))]
 fn main() {
     let cli = Cli::parse();
     let mut connection = establish_connection();
 
     match cli.subcommand {
         SubCommand::Create { title, body } => {
-            Post::insert(
-                &mut connection,
-                NewPost {
-                    title: &title,
-                    body: &body,
-                    published: false,
-                },
-            )
-            .unwrap();
+            Post::insert(&mut connection, NewPost::new(title, body, false)).unwrap();
 
             println!("{}", "Post created successfully".blue().bold());
         }
@@ -36,8 +40,10 @@ fn main() {
             println!("{:#?}", posts);
         }
         SubCommand::Get { id } => {
-            let post = Post::get_by_id(&mut connection, &id).unwrap();
-            println!("{:#?}", post);
+            match Post::find(&mut connection, &id).unwrap() {
+                Some(post) => println!("{:#?}", post),
+                None => println!("{}", format!("No post with id {}", id).red().bold()),
+            }
         }
         SubCommand::GetByPublished { published } => {
             let posts = Post::get_by_published(&mut connection, &published).unwrap();
@@ -53,21 +59,20 @@ fn main() {
             body,
             published,
         } => {
-            if let Some(title) = title {
-                Post::update_titles_by_id(&mut connection, &id, &title).unwrap();
-
-                println!("{}", "Title updated successfully".blue().bold());
+            let changeset = PostChangeset {
+                title,
+                body,
+                published,
+            };
+
+            if changeset.is_empty() {
+                println!("{}", "Nothing to update".red().bold());
+                return;
             }
-            if let Some(body) = body {
-                Post::update_bodys_by_id(&mut connection, &id, &body).unwrap();
 
-                println!("{}", "Body updated successfully".blue().bold());
-            }
-            if let Some(published) = published {
-                Post::update_publisheds_by_id(&mut connection, &id, &published).unwrap();
+            Post::update_by_id(&mut connection, &id, changeset).unwrap();
 
-                println!("{}", "Published updated successfully".blue().bold());
-            }
+            println!("{}", "Post updated successfully".blue().bold());
         }
         SubCommand::Delete { id } => {
             Post::delete_by_id(&mut connection, &id).unwrap();
@@ -92,6 +97,7 @@ fn main() {
     }
 }
 
+#[cfg(not(any(feature = "diesel2", feature = "serde")))]
 fn establish_connection() -> PgConnection {
     // read from env
     dotenv::dotenv().ok();
@@ -1,19 +1,12 @@
-use crate::diesel::pg::PgConnection;
 use crate::schema::*;
 
-#[diesel_ease::diesel_ease(PgConnection)]
+// `NewPost` is generated from the fields which are not `#[ease(auto)]`
+#[diesel_ease::diesel_ease(diesel::pg::PgConnection)]
 #[derive(Queryable, Debug, Clone, PartialEq, Eq)]
 pub struct Post {
+    #[ease(auto, immutable)]
     pub id: i32,
     pub title: String,
     pub body: String,
     pub published: bool,
 }
-
-#[derive(Insertable, Debug, Clone, PartialEq, Eq)]
-#[table_name = "posts"]
-pub struct NewPost<'a> {
-    pub title: &'a str,
-    pub body: &'a str,
-    pub published: bool,
-}
@@ -0,0 +1,213 @@
+//! Arguments of the `#[diesel_ease(...)]` attribute.
+
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::*;
+
+use crate::connection::{Backend, Connection};
+use crate::naming::Naming;
+use crate::pool::Pool;
+
+/// Arguments given to the macro. e.g. `#[diesel_ease(PgConnection, table = "users", schema = db::schema)]`
+pub(crate) struct Args {
+    /// The connection the generated functions take.
+    pub connection: Connection,
+
+    /// The table, if given with `table = "users"` or `table = path::to::users`.
+    pub table: Option<Path>,
+
+    /// Path of the module containing the tables, if given with `schema = path::to::schema`.
+    pub schema: Option<Path>,
+
+    /// The type `insert` takes, if given with `insertable = NewUser`.
+    pub insertable: Option<Type>,
+
+    /// The groups of functions to generate, from `ops(...)` and `exclude(...)`.
+    pub ops: Ops,
+
+    /// How the functions are named, from `naming = ...`. English plural by default.
+    pub naming: Naming,
+
+    /// The pool the `*_pooled` functions take, if given with `pool = r2d2` or `pool = deadpool`.
+    pub pool: Option<Pool>,
+
+    /// The backend of the connection type, if given with `backend = mysql`. Without it the changed rows are returned
+    /// with `RETURNING`.
+    pub backend: Option<Backend>,
+}
+
+/// A group of generated functions, e.g. `get_by` for all the `get_by_*` functions.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Op {
+    Get,
+    GetBy,
+    Update,
+    Insert,
+    DeleteBy,
+    GetAll,
+    DeleteAll,
+    Find,
+    Query,
+}
+
+impl Op {
+    /// All the groups with their names in `ops(...)` and `exclude(...)`.
+    const ALL: &'static [(&'static str, Op)] = &[
+        ("get", Op::Get),
+        ("get_by", Op::GetBy),
+        ("update", Op::Update),
+        ("insert", Op::Insert),
+        ("delete_by", Op::DeleteBy),
+        ("get_all", Op::GetAll),
+        ("delete_all", Op::DeleteAll),
+        ("find", Op::Find),
+        ("query", Op::Query),
+    ];
+
+    /// Parse a list of groups, e.g. `(get, get_by, insert)`.
+    fn parse_list(input: ParseStream) -> Result<Vec<Op>> {
+        let content;
+        parenthesized!(content in input);
+
+        Punctuated::<Ident, Token![,]>::parse_terminated(&content)?
+            .iter()
+            .map(|name| {
+                Op::ALL
+                    .iter()
+                    .find(|(op_name, _)| name == op_name)
+                    .map(|(_, op)| *op)
+                    .ok_or_else(|| {
+                        let names: Vec<String> = Op::ALL.iter().map(|(op_name, _)| format!("`{}`", op_name)).collect();
+
+                        Error::new_spanned(
+                            name,
+                            format!("unknown operation `{}`, expected one of {}", name, names.join(", ")),
+                        )
+                    })
+            })
+            .collect()
+    }
+}
+
+/// The groups of functions to generate.
+pub(crate) struct Ops(Vec<Op>);
+
+impl Ops {
+    /// Whether the functions of the group are generated.
+    pub fn contains(&self, op: Op) -> bool {
+        self.0.contains(&op)
+    }
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut connection = None;
+        let mut table = None;
+        let mut schema = None;
+        let mut insertable = None;
+        let mut ops = None;
+        let mut exclude = None;
+        let mut naming = None;
+        let mut pool = None;
+        let mut backend = None;
+
+        while !input.is_empty() {
+            if input.peek(Ident) && input.peek2(token::Paren) {
+                let key: Ident = input.parse()?;
+
+                match key.to_string().as_str() {
+                    "ops" => set_once(&mut ops, &key, Op::parse_list(input)?)?,
+                    "exclude" => set_once(&mut exclude, &key, Op::parse_list(input)?)?,
+                    _ => {
+                        return Err(Error::new_spanned(
+                            &key,
+                            format!("unknown argument `{}(...)`, expected `ops(...)` or `exclude(...)`", key),
+                        ))
+                    }
+                }
+            } else if input.peek(Ident) && input.peek2(Token![=]) {
+                let key: Ident = input.parse()?;
+                input.parse::<Token![=]>()?;
+
+                match key.to_string().as_str() {
+                    "table" => {
+                        let value = if input.peek(LitStr) {
+                            Path::from(input.parse::<LitStr>()?.parse::<Ident>()?)
+                        } else {
+                            input.parse()?
+                        };
+
+                        set_once(&mut table, &key, value)?;
+                    }
+                    "schema" => set_once(&mut schema, &key, input.parse()?)?,
+                    "insertable" => set_once(&mut insertable, &key, input.parse()?)?,
+                    "naming" => set_once(&mut naming, &key, Naming::parse(input)?)?,
+                    "pool" => set_once(&mut pool, &key, Pool::parse(input)?)?,
+                    "backend" => set_once(&mut backend, &key, Backend::parse(input)?)?,
+                    _ => {
+                        return Err(Error::new_spanned(
+                            &key,
+                            format!(
+                                "unknown argument `{}`, expected one of `table`, `schema`, `insertable`, `naming`, `pool`, `backend`",
+                                key
+                            ),
+                        ))
+                    }
+                }
+            } else {
+                let path: Path = input.parse().map_err(|error| {
+                    Error::new(
+                        error.span(),
+                        "expected a connection type such as `PgConnection` or `diesel::pg::PgConnection`, or `generic`",
+                    )
+                })?;
+
+                if connection.is_some() {
+                    return Err(Error::new_spanned(path, "connection type is given more than once"));
+                }
+
+                connection = Some(if path.is_ident("generic") {
+                    Connection::Generic
+                } else {
+                    Connection::Type(path)
+                });
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        let connection = connection.ok_or_else(|| {
+            Error::new(
+                proc_macro2::Span::call_site(),
+                "missing connection type, e.g. `#[diesel_ease(PgConnection)]`",
+            )
+        })?;
+
+        let mut ops = ops.unwrap_or_else(|| Op::ALL.iter().map(|(_, op)| *op).collect());
+        ops.retain(|op| !exclude.iter().flatten().any(|excluded| excluded == op));
+
+        Ok(Args {
+            connection,
+            table,
+            schema,
+            insertable,
+            ops: Ops(ops),
+            naming: naming.unwrap_or_default(),
+            pool,
+            backend,
+        })
+    }
+}
+
+/// Set the value of an argument, failing if it was already given.
+fn set_once<T>(slot: &mut Option<T>, key: &Ident, value: T) -> Result<()> {
+    if slot.is_some() {
+        return Err(Error::new_spanned(key, format!("`{}` is given more than once", key)));
+    }
+
+    *slot = Some(value);
+
+    Ok(())
+}
@@ -0,0 +1,277 @@
+//! The connection the generated functions take.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::ParseStream;
+use syn::*;
+
+use crate::version::DIESEL2;
+
+/// Connection given to the macro. e.g. `#[diesel_ease(PgConnection)]` or `#[diesel_ease(generic)]`
+pub(crate) enum Connection {
+    /// A connection type like `PgConnection` or `diesel::sqlite::SqliteConnection`.
+    Type(Path),
+
+    /// The functions are generic over `C: diesel::Connection`, so one model can be used with several backends.
+    Generic,
+
+    /// The `async` functions are generic over `C: diesel_async::AsyncConnection`, e.g. `AsyncPgConnection`.
+    ///
+    /// They are generated next to the functions of the connection given to the macro, with the `async` feature.
+    Async,
+}
+
+impl Connection {
+    /// Generic parameters of a generated function.
+    pub fn generics(&self) -> TokenStream {
+        self.generics_with(&[], &[])
+    }
+
+    /// Generic parameters of a generated function that has its own `lifetimes` and `types` parameters.
+    pub fn generics_with(&self, lifetimes: &[Lifetime], types: &[Ident]) -> TokenStream {
+        let (connection_lifetime, connection_type) = match self {
+            Connection::Type(_) => (vec![], vec![]),
+            Connection::Generic | Connection::Async => (vec![quote! { 'q }], vec![quote! { C }]),
+        };
+
+        if lifetimes.is_empty() && types.is_empty() && connection_type.is_empty() {
+            return quote! {};
+        }
+
+        quote! { <#(#connection_lifetime,)* #(#lifetimes,)* #(#connection_type,)* #(#types,)*> }
+    }
+
+    /// Lifetimes a function needs to declare when its own bounds load a query.
+    ///
+    /// diesel 2 names the lifetime of the query in `LoadQuery<'q, ...>`. In generic mode it is already declared.
+    pub fn query_lifetimes(&self) -> Vec<Lifetime> {
+        match self {
+            Connection::Type(_) if DIESEL2 => vec![parse_quote!('q)],
+            _ => vec![],
+        }
+    }
+
+    /// Type of the `connection` parameter, behind `&mut`.
+    pub fn ty(&self) -> TokenStream {
+        match self {
+            Connection::Type(path) => quote! { #path },
+            Connection::Generic | Connection::Async => quote! { C },
+        }
+    }
+
+    /// Reference for the parameters that end up in the query.
+    ///
+    /// In generic mode the lifetime is named, so that the bounds can name the type of the query. The async functions
+    /// take the parameters by value, bounds naming a borrowed parameter would make their futures not `Send`.
+    pub fn param_ref(&self) -> TokenStream {
+        match self {
+            Connection::Type(_) => quote! { & },
+            Connection::Generic => quote! { &'q },
+            Connection::Async => quote! {},
+        }
+    }
+
+    /// Where clause of a generated function. `bounds` are only needed in generic mode.
+    pub fn where_clause(&self, bounds: &[TokenStream]) -> TokenStream {
+        self.where_clause_with(bounds, &[])
+    }
+
+    /// Where clause of a generated function that has its own generic parameters.
+    ///
+    /// `bounds` are only needed in generic mode, `own_bounds` are always needed.
+    pub fn where_clause_with(&self, bounds: &[TokenStream], own_bounds: &[TokenStream]) -> TokenStream {
+        match self {
+            Connection::Type(_) if own_bounds.is_empty() => quote! {},
+            Connection::Type(_) => quote! {
+                where
+                    #(#own_bounds,)*
+            },
+            Connection::Generic => quote! {
+                where
+                    C: diesel::Connection,
+                    #(#bounds,)*
+                    #(#own_bounds,)*
+            },
+            Connection::Async => quote! {
+                where
+                    C: diesel_async::AsyncConnection,
+                    #(#bounds,)*
+                    #(#own_bounds,)*
+            },
+        }
+    }
+
+    /// Bound for loading `output` rows from the `query` type.
+    pub fn load_bound(&self, query: TokenStream, output: TokenStream) -> TokenStream {
+        let connection = self.ty();
+
+        if let Connection::Async = self {
+            // the query is moved into the future, which is `Send`.
+            quote! { #query: diesel_async::methods::LoadQuery<'q, #connection, #output> + Send + 'q }
+        } else if DIESEL2 {
+            quote! { #query: diesel::query_dsl::LoadQuery<'q, #connection, #output> }
+        } else {
+            quote! { #query: diesel::query_dsl::LoadQuery<#connection, #output> }
+        }
+    }
+
+    /// Backend of the connection.
+    pub fn backend(&self) -> TokenStream {
+        let connection = self.ty();
+
+        match self {
+            Connection::Async => quote! { <#connection as diesel_async::AsyncConnection>::Backend },
+            _ => quote! { <#connection as diesel::Connection>::Backend },
+        }
+    }
+
+    /// Select whole rows of the `model` from the `query`, returns the new query and its type.
+    ///
+    /// With diesel 2 the columns are selected with `Selectable`, with diesel 1 the query is not changed.
+    pub fn select_model(&self, model: &Ident, query: TokenStream, query_type: TokenStream) -> (TokenStream, TokenStream) {
+        if !DIESEL2 {
+            return (query, query_type);
+        }
+
+        let backend = self.backend();
+
+        (
+            quote! { #query.select(#model::as_select()) },
+            quote! { diesel::dsl::Select<#query_type, diesel::dsl::AsSelect<#model, #backend>> },
+        )
+    }
+
+    /// Bound for executing the `statement` type.
+    pub fn execute_bound(&self, statement: TokenStream) -> TokenStream {
+        let connection = self.ty();
+
+        if let Connection::Async = self {
+            return quote! { #statement: diesel_async::methods::ExecuteDsl<#connection> + Send + 'q };
+        }
+
+        quote! { #statement: diesel::query_dsl::methods::ExecuteDsl<#connection> }
+    }
+
+    /// Name of a generated function, `_async` is appended for an async connection.
+    pub fn fn_name(&self, name: Ident) -> Ident {
+        match self {
+            Connection::Async => format_ident!("{}_async", name),
+            _ => name,
+        }
+    }
+
+    /// `async` for the functions taking an async connection.
+    pub fn asyncness(&self) -> TokenStream {
+        match self {
+            Connection::Async => quote! { async },
+            _ => quote! {},
+        }
+    }
+
+    /// Run the `query` on `connection` with a method of `RunQueryDsl`, e.g. `load::<User>`.
+    ///
+    /// The async functions call the method of diesel-async by its path and await it, the `RunQueryDsl` of diesel is
+    /// also in scope when the module of the struct imports `diesel::prelude::*`.
+    pub fn run(&self, query: TokenStream, method: TokenStream) -> TokenStream {
+        match self {
+            Connection::Async => quote! { diesel_async::RunQueryDsl::#method(#query, connection).await },
+            _ => quote! { #query.#method(connection) },
+        }
+    }
+
+    /// Imports of the generated function bodies, diesel-async's `RunQueryDsl` is called by its path.
+    pub fn prelude(&self) -> TokenStream {
+        match self {
+            Connection::Async => quote! {
+                use diesel::prelude::{
+                    BoolExpressionMethods, ExpressionMethods, NullableExpressionMethods, OptionalExtension, QueryDsl,
+                    SelectableHelper, TextExpressionMethods,
+                };
+            },
+            _ => quote! { use diesel::prelude::*; },
+        }
+    }
+
+    /// Whether the changed rows are returned with `RETURNING`, `backend` is given with `backend = ...`.
+    ///
+    /// With `backend = mysql` or `backend = sqlite` the changed rows are loaded again after the statement. Without it
+    /// `RETURNING` is used, so on a backend without it the functions returning the changed rows do not compile. In
+    /// generic mode `RETURNING` is always used, the row inserted last can only be found knowing the backend.
+    pub fn supports_returning(&self, backend: Option<Backend>) -> bool {
+        !matches!((self, backend), (Connection::Type(_), Some(Backend::Mysql | Backend::Sqlite)))
+    }
+
+    /// Condition matching the row inserted last on the connection, for a backend without `RETURNING`.
+    ///
+    /// SQLite has the `rowid` of the row. MySQL only has the value of an `AUTO_INCREMENT` column, so it needs a
+    /// primary key of one column.
+    pub fn last_insert_condition(&self, backend: Option<Backend>, table_module: &Path, primary_key: &[&Ident]) -> Option<TokenStream> {
+        match (self, backend, primary_key) {
+            (Connection::Type(_), Some(Backend::Sqlite), _) => Some(quote! {
+                diesel::dsl::sql::<diesel::sql_types::Bool>("rowid = last_insert_rowid()")
+            }),
+            (Connection::Type(_), Some(Backend::Mysql), [primary_key]) => Some(quote! {
+                #table_module::#primary_key.eq(
+                    diesel::dsl::sql::<<#table_module::#primary_key as diesel::Expression>::SqlType>("LAST_INSERT_ID()"),
+                )
+            }),
+            _ => None,
+        }
+    }
+
+    /// Check that the backend of the connection type is `backend`, so that a wrong `backend = ...` does not compile.
+    pub fn check_backend(&self, backend: Option<Backend>) -> TokenStream {
+        match (self, backend) {
+            (Connection::Type(path), Some(backend)) => {
+                let backend_type = backend.ty();
+
+                quote! {
+                    const _: () = {
+                        fn check<C: diesel::Connection<Backend = #backend_type>>() {}
+
+                        fn check_connection() {
+                            check::<#path>();
+                        }
+                    };
+                }
+            }
+            _ => quote! {},
+        }
+    }
+}
+
+/// The backend of the connection type, from `backend = ...`. It tells how the changed rows are loaded again.
+#[derive(Clone, Copy)]
+pub(crate) enum Backend {
+    /// PostgreSQL, the changed rows are returned with `RETURNING`.
+    Pg,
+
+    /// MySQL, the inserted row is loaded by `LAST_INSERT_ID()`.
+    Mysql,
+
+    /// SQLite, the inserted row is loaded by `last_insert_rowid()`.
+    Sqlite,
+}
+
+impl Backend {
+    /// Parse the value of `backend = ...`, `pg`, `mysql` or `sqlite`.
+    pub fn parse(input: ParseStream) -> Result<Self> {
+        let name: Ident = input.parse()?;
+
+        match name.to_string().as_str() {
+            "pg" => Ok(Backend::Pg),
+            "mysql" => Ok(Backend::Mysql),
+            "sqlite" => Ok(Backend::Sqlite),
+            _ => Err(Error::new_spanned(name, "expected `pg`, `mysql` or `sqlite`")),
+        }
+    }
+
+    /// The backend type of diesel.
+    fn ty(&self) -> TokenStream {
+        match self {
+            Backend::Pg => quote! { diesel::pg::Pg },
+            Backend::Mysql => quote! { diesel::mysql::Mysql },
+            Backend::Sqlite => quote! { diesel::sqlite::Sqlite },
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! `#[ease(...)]` attributes on the fields of the struct.
+
+use syn::*;
+
+/// Options given to a field with `#[ease(...)]`.
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    /// The database sets the value, e.g. `id` or `created_at`. It is left out of the generated insertable.
+    pub auto: bool,
+
+    /// No functions are generated for the field.
+    pub skip: bool,
+
+    /// No functions filter by the field, e.g. for large text bodies or secrets.
+    pub no_filter: bool,
+
+    /// The field is not used by update and delete functions, neither as the new value nor as the filter.
+    pub readonly: bool,
+
+    /// The field is never updated, but it can still be the filter of update and delete functions.
+    pub immutable: bool,
+
+    /// The field is part of the primary key. Without it, a field named `id` is the primary key.
+    pub primary_key: bool,
+
+    /// At most one row has a given value of the field, e.g. an email or a slug. Lookups by it return one row.
+    pub unique: bool,
+
+    /// The rows can be paged through in the order of the field with `get_after_*` and `get_before_*`, e.g. `id` or
+    /// `created_at`.
+    pub cursor: bool,
+
+    /// The name of the field in the names of the generated functions, given with `#[ease(rename = "...")]`.
+    pub rename: Option<String>,
+}
+
+impl FieldAttrs {
+    /// Parse the `#[ease(...)]` attributes of the field.
+    pub fn parse(field: &Field) -> Result<Self> {
+        let mut attrs = FieldAttrs::default();
+
+        for nested in ease_attrs(&field.attrs)? {
+            if let NestedMeta::Meta(Meta::NameValue(name_value)) = &nested {
+                if name_value.path.is_ident("rename") {
+                    match &name_value.lit {
+                        Lit::Str(rename) if rename.parse::<Ident>().is_ok() => attrs.rename = Some(rename.value()),
+                        lit => return Err(Error::new_spanned(lit, "expected a name like `#[ease(rename = \"status\")]`")),
+                    }
+
+                    continue;
+                }
+            }
+
+            let flag = match &nested {
+                NestedMeta::Meta(Meta::Path(path)) => path.get_ident().map(Ident::to_string),
+                _ => None,
+            };
+
+            match flag.as_deref() {
+                Some("auto") => attrs.auto = true,
+                Some("skip") => attrs.skip = true,
+                Some("no_filter") => attrs.no_filter = true,
+                Some("readonly") => attrs.readonly = true,
+                Some("immutable") => attrs.immutable = true,
+                Some("primary_key") => attrs.primary_key = true,
+                Some("unique") => attrs.unique = true,
+                Some("cursor") => attrs.cursor = true,
+                _ => {
+                    return Err(Error::new_spanned(
+                        nested,
+                        "unknown field option, expected one of `auto`, `skip`, `no_filter`, `readonly`, `immutable`, `primary_key`, `unique`, `cursor`, `rename`",
+                    ))
+                }
+            }
+        }
+
+        Ok(attrs)
+    }
+}
+
+/// The options in all `#[ease(...)]` attributes.
+pub(crate) fn ease_attrs(attrs: &[Attribute]) -> Result<Vec<NestedMeta>> {
+    let mut options = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("ease")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => options.extend(list.nested),
+            meta => return Err(Error::new_spanned(meta, "expected `#[ease(...)]`")),
+        }
+    }
+
+    Ok(options)
+}
+
+/// The attributes of a field which are copied to the fields of the generated structs.
+///
+/// Only the docs and the attributes of the diesel derives, other attributes (e.g. `#[serde(...)]`) belong to derives
+/// of the model, which the generated structs do not have.
+pub(crate) fn derive_attrs(field: &Field) -> Vec<&Attribute> {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc") || attr.path.is_ident("column_name") || attr.path.is_ident("diesel"))
+        .collect()
+}
+
+/// Remove the `#[ease(...)]` attributes from the struct and its fields.
+///
+/// They are only read by this macro, the compiler does not know them.
+pub(crate) fn strip_ease_attrs(input: &mut ItemStruct) {
+    input.attrs.retain(|attr| !attr.path.is_ident("ease"));
+
+    for field in input.fields.iter_mut() {
+        field.attrs.retain(|attr| !attr.path.is_ident("ease"));
+    }
+}
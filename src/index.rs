@@ -0,0 +1,63 @@
+//! `#[ease(index(...))]` attributes on the struct, the combinations of fields the rows are looked up by. All the fields of
+//! an index have to match, `OR` lookups are left to `query()`.
+
+use syn::*;
+
+use crate::field::{ease_attrs, FieldAttrs};
+
+/// Parse the `#[ease(index(...))]` attributes of the struct, each is the positions of its fields in `fields`.
+///
+/// An index needs at least two fields, the functions filtering by one field are already generated for every field.
+pub(crate) fn parse_indexes(attrs: &[Attribute], fields: &[Ident], fields_attrs: &[FieldAttrs]) -> Result<Vec<Vec<usize>>> {
+    let mut indexes = Vec::new();
+
+    for nested in ease_attrs(attrs)? {
+        let list = match &nested {
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("index") => list,
+            _ => return Err(Error::new_spanned(nested, "unknown struct option, expected `index(...)`")),
+        };
+
+        let mut index: Vec<usize> = Vec::new();
+
+        for nested in &list.nested {
+            let name = match nested {
+                NestedMeta::Meta(Meta::Path(path)) => path.get_ident(),
+                _ => None,
+            }
+            .ok_or_else(|| Error::new_spanned(nested, "expected a field like `#[ease(index(title, published))]`"))?;
+
+            let i = fields
+                .iter()
+                .position(|field| field == name)
+                .ok_or_else(|| Error::new_spanned(name, format!("the struct has no field `{}`", name)))?;
+
+            if fields_attrs[i].skip || fields_attrs[i].no_filter {
+                return Err(Error::new_spanned(
+                    name,
+                    format!("`{}` is `#[ease(skip)]` or `#[ease(no_filter)]`, no functions filter by it", name),
+                ));
+            }
+
+            if index.contains(&i) {
+                return Err(Error::new_spanned(name, format!("`{}` is given more than once", name)));
+            }
+
+            index.push(i);
+        }
+
+        if index.len() < 2 {
+            return Err(Error::new_spanned(
+                list,
+                "an index needs at least two fields, the functions filtering by one field are always generated",
+            ));
+        }
+
+        if indexes.contains(&index) {
+            return Err(Error::new_spanned(list, "the same index is given more than once"));
+        }
+
+        indexes.push(index);
+    }
+
+    Ok(indexes)
+}
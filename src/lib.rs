@@ -2,481 +2,2647 @@
 
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
+use syn::ext::IdentExt;
 use syn::*; 
 
+mod args;
+mod connection;
+mod field;
+mod index;
+mod naming;
+mod pool;
+mod version;
+
+use args::{Args, Op};
+use connection::Connection;
+use field::FieldAttrs;
+
 /// A macro to generate useful associated functions for database operations for the given struct.
 /// 
 /// See the [module](index.html) for more information.
 #[proc_macro_attribute]
 pub fn diesel_ease(args: TokenStream, input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as ItemStruct);
-    let struct_name = &input.ident;
+    let mut input = parse_macro_input!(input as Item);
 
-    let struct_name_lower = struct_name.to_string().to_lowercase();
+    match expand(args.into(), &input) {
+        Ok(expanded) => expanded.into(),
+        Err(error) => {
+            let error = error.to_compile_error();
 
-    // name of the table in the database. e.g. If the struct name is `User`, the table name is `users`.
-    let struct_module_name = format!("{}s", struct_name_lower);
+            if let Item::Struct(input) = &mut input {
+                field::strip_ease_attrs(input);
+            }
 
-    let struct_module_name = Ident::new(&struct_module_name, struct_name.span());
+            // keep the item so that the error is the only one the user sees.
+            quote! {
+                #input
+                #error
+            }
+            .into()
+        }
+    }
+}
 
-    let connection_type = parse_macro_input!(args as Ident);
+/// Generate the struct and its functions.
+///
+/// Returns an error pointing at the attribute argument, field or type that is not supported.
+fn expand(args: proc_macro2::TokenStream, input: &Item) -> Result<proc_macro2::TokenStream> {
+    let input = match input {
+        Item::Struct(input) => input,
+        other => {
+            return Err(Error::new_spanned(
+                other,
+                "`diesel_ease` can only be used on structs with named fields",
+            ))
+        }
+    };
 
-    let fields = &input.fields;
+    let struct_name = &input.ident;
 
-    if let Fields::Named(named_fields) = fields {
-        let fields = &named_fields.named;
+    let struct_name_lower = naming::to_snake_case(&struct_name.to_string());
 
-        // fields of the struct.
-        let fields_name: Vec<Ident> = fields.iter().map(|x| x.ident.clone().unwrap()).collect();
+    let args: Args = parse2(args)?;
 
-        // types of the fields of the struct.
-        let fields_type: Vec<Type> = fields.iter().map(|x| x.ty.clone()).collect();
+    let table = match args.table {
+        Some(table) => Some(table),
+        None => table_from_attrs(&input.attrs)?,
+    };
 
-        let fields_type: Vec<Ident> = fields_type
-            .iter()
-            .map(|x| {
-                if let Type::Path(path) = x {
-                    path.path.segments.first().unwrap().ident.clone()
-                } else {
-                    panic!("Not a path")
-                }
-            })
-            .collect();
+    // path of the module of the table. e.g. If the struct name is `BlogPost`, the module is `crate::schema::blog_posts`.
+    let table_module: Path = match table {
+        Some(table) if table.get_ident().is_none() => {
+            if let Some(schema) = &args.schema {
+                return Err(Error::new_spanned(
+                    schema,
+                    "`schema` cannot be used when the table is given as a path",
+                ));
+            }
 
-        // name of functions for get functions
-        let mut fn_names_get: Vec<Ident> = Vec::new();
+            table
+        }
+        table => {
+            let schema = args.schema.unwrap_or_else(|| parse_quote!(crate::schema));
 
-        // name of functions for get2 functions
-        let mut fn_names_get2: Vec<Ident> = Vec::new();
-        
-        // name of functions for update functions
-        let mut fn_names_update = Vec::new();
-        
-        // name of functions for delete functions
-        let mut fn_names_delete = Vec::new();
+            let table = match table.as_ref().and_then(Path::get_ident) {
+                Some(table) => table.clone(),
+                None => Ident::new(&naming::pluralize(&struct_name_lower), struct_name.span()),
+            };
 
-        // return type of functions
-        let mut fn_return_types = Vec::new();
+            parse_quote!(#schema::#table)
+        }
+    };
 
-        // parameters of functions. This variable contains "query_{param}"
-        let mut params_for_get = Vec::new();
+    // name of the table in the database. e.g. If the struct name is `BlogPost`, the table name is `blog_posts`.
+    let struct_module_name = table_module.segments.last().unwrap().ident.clone();
 
-        // parameters of functions. This variable contains "{param}"
-        let mut param_names_for_get= Vec::new();
+    let connection = args.connection;
 
-        // types of the parameters.
-        let mut param_types_for_get= Vec::new();
+    if let (Some(_), Connection::Generic) = (args.backend, &connection) {
+        return Err(Error::new(
+            proc_macro2::Span::call_site(),
+            "`backend` needs a connection type, e.g. `#[diesel_ease(Db, backend = mysql)]`",
+        ));
+    }
 
-        // parameters for delete functions.
-        let mut params_for_delete_get2 = vec![];
+    // type of the table, for the bounds of generic functions.
+    let table_type = quote! { #table_module::table };
 
-        // parameters for delete functions.
-        let mut params_names_for_delete_get2 = vec![];
+    let fields = named_fields(input)?;
 
-        // parameter types for delete functions.
-        let mut param_types_for_delete_get2 = Vec::new();
-        
-        // fields to get from db.
-        // NOTE: The variable `fields_name` and this is the same. `fields_name` will only contain struct fields one time. But this variable can contain same fields multiple times.
-        let mut fields = Vec::new();
+    // the struct without the `#[ease(...)]` attributes.
+    let mut item = input.clone();
+    field::strip_ease_attrs(&mut item);
 
-        // parameter for update functions
-        let mut new_fields_params = Vec::new();
-        
-        // parameter for update functions
-        let mut new_fields_names = Vec::new();
-        
-        // parameter types for update functions
-        let mut new_fields_types = Vec::new();   
-        
-        // parameter for insert functions
-        let params_for_insert = format_ident!("new_{}", struct_name_lower);
-        
-        // parameter types for insert functions
-        let param_types_for_insert = format_ident!("New{}", struct_name);
+    // with diesel 2 the model is also `Selectable`.
+    item.attrs.extend(version::model_attrs(&input.attrs, &table_module, &connection)?);
 
+    // options of the fields given with `#[ease(...)]`.
+    let fields_attrs: Vec<FieldAttrs> = fields.iter().map(FieldAttrs::parse).collect::<Result<_>>()?;
 
-        /*
-            * `field2` is a variable through which we can get `field`.
-            * `i` is used to get the type of `field`. `i` is incremented in outer loop. That means `i` will be the same for `field`'s index.
-            * `j` is used to get the type of `field2`. `j` is incremented in inner loop. That means `j` will be the same for `field2`'s index.
-            
+    // fields of the struct.
+    let fields_name: Vec<Ident> = fields.iter().map(|x| x.ident.clone().unwrap()).collect();
 
-        */
+    // types of the fields of the struct.
+    let fields_type: Vec<Type> = fields.iter().map(|x| x.ty.clone()).collect();
 
-        for (i, field) in fields_name.iter().enumerate() {
-            for (j, field2) in fields_name.iter().enumerate() {
-                if field != field2 {
-                    fn_names_get.push(format_ident!("get_{}s_by_{}", field, field2));
+    // names of the fields in the names of the functions, they can be given with `#[ease(rename = "...")]`.
+    let fields_fn_name: Vec<String> = fields_name
+        .iter()
+        .zip(&fields_attrs)
+        .map(|(field, attrs)| attrs.rename.clone().unwrap_or_else(|| field.unraw().to_string()))
+        .collect();
 
-                    fn_names_update.push(format_ident!("update_{}s_by_{}", field, field2));
+    for (i, name) in fields_fn_name.iter().enumerate() {
+        if fields_fn_name[..i].contains(name) {
+            return Err(Error::new_spanned(
+                &fields[i],
+                format!("another field is already named `{}` in the function names, use `#[ease(rename = \"...\")]`", name),
+            ));
+        }
+    }
 
-                    fn_return_types.push(fields_type[i].clone());
+    // combinations of fields the rows are looked up by, from `#[ease(index(...))]` on the struct.
+    let indexes = index::parse_indexes(&input.attrs, &fields_name, &fields_attrs)?;
 
-                    params_for_get.push(format_ident!("query_{}", field2));
+    // fields of the primary key, from `#[ease(primary_key)]` or else a field named `id`.
+    let primary_key: Vec<usize> = if fields_attrs.iter().any(|attrs| attrs.primary_key) {
+        (0..fields_name.len()).filter(|&i| fields_attrs[i].primary_key).collect()
+    } else {
+        fields_name.iter().position(|field| field == "id").into_iter().collect()
+    };
 
-                    param_names_for_get.push(field2.clone());
+    // fields marked with `#[ease(cursor)]`, the rows can be paged through in their order.
+    let cursor_fields: Vec<usize> = (0..fields_name.len()).filter(|&i| fields_attrs[i].cursor).collect();
 
-                    param_types_for_get.push(fields_type[j].clone());
+    for &i in &cursor_fields {
+        if fields_attrs[i].skip {
+            return Err(Error::new_spanned(
+                &fields[i],
+                "`#[ease(cursor)]` cannot be used with `#[ease(skip)]`, no functions are generated for a skipped field",
+            ));
+        }
+
+        if option_inner(&fields_type[i]).is_some() {
+            return Err(Error::new_spanned(
+                &fields[i],
+                "`#[ease(cursor)]` cannot be used on a nullable field, the rows with `NULL` would never be reached",
+            ));
+        }
+    }
+
+    // the fields a cursor orders the rows by: the cursor field, then the primary key so that the rows with the
+    // same value as the cursor are not skipped. A unique field is enough on its own.
+    let mut cursor_keys: Vec<Vec<usize>> = Vec::new();
+
+    for &i in &cursor_fields {
+        if fields_attrs[i].unique || primary_key == [i] {
+            cursor_keys.push(vec![i]);
+        } else if primary_key.is_empty() {
+            return Err(Error::new_spanned(
+                &fields[i],
+                "`#[ease(cursor)]` needs a primary key to order the rows with the same value, or the field must be `#[ease(unique)]`",
+            ));
+        } else {
+            cursor_keys.push(std::iter::once(i).chain(primary_key.iter().copied().filter(|&j| j != i)).collect());
+        }
+    }
 
-                    fields.push(field.clone());
+    // the opaque cursors, one per cursor field, with the values of the fields the rows are ordered by.
+    let cursor_names: Vec<Ident> = cursor_fields
+        .iter()
+        .map(|&i| format_ident!("{}{}Cursor", struct_name, naming::to_camel_case(&fields_name[i].unraw().to_string())))
+        .collect();
 
-                    new_fields_params.push(format_ident!("new_{}", field.clone()));
+    // the insertable struct. It is generated when some fields are set by the database.
+    let generated_insertable = if fields_attrs.iter().any(|attrs| attrs.auto) {
+        let insertable_name = format_ident!("New{}", struct_name);
+        let table_name_attr = version::table_name_attr(&table_module);
+        let vis = &item.vis;
+
+        let insertable_fields: Vec<&Field> = item
+            .fields
+            .iter()
+            .zip(&fields_attrs)
+            .filter(|(_, attrs)| !attrs.auto)
+            .map(|(field, _)| field)
+            .collect();
 
-                    new_fields_names.push(field.clone());
+        let insertable_attrs: Vec<Vec<&Attribute>> = insertable_fields.iter().map(|field| field::derive_attrs(field)).collect();
+        let insertable_vis: Vec<&Visibility> = insertable_fields.iter().map(|field| &field.vis).collect();
+        let insertable_names: Vec<&Option<Ident>> = insertable_fields.iter().map(|field| &field.ident).collect();
+        let insertable_types: Vec<&Type> = insertable_fields.iter().map(|field| &field.ty).collect();
 
-                    new_fields_types.push(fields_type[i].clone());
-                }
+        let doc_insertable = format!(
+            "A new [`{}`] for inserting. It has the fields of [`{}`] which are not `#[ease(auto)]`.",
+            struct_name, struct_name
+        );
+        let doc_new = format!("Create a new [`{}`]", insertable_name);
 
+        quote! {
+            #[doc = #doc_insertable]
+            #[derive(diesel::Insertable)]
+            #table_name_attr
+            #vis struct #insertable_name {
+                #(
+                    #(#insertable_attrs)*
+                    #insertable_vis #insertable_names: #insertable_types,
+                )*
             }
 
-            fn_names_delete.push(format_ident!("delete_by_{}", field));
+            impl #insertable_name {
+                #[doc = #doc_new]
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(#(#insertable_names: #insertable_types),*) -> Self {
+                    #insertable_name {
+                        #(#insertable_names,)*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // fields that can be changed by the changeset, the others are never updated.
+    let changeset_fields: Vec<&Field> = item
+        .fields
+        .iter()
+        .zip(&fields_attrs)
+        .filter(|(_, attrs)| !attrs.skip && !attrs.immutable && !attrs.readonly)
+        .map(|(field, _)| field)
+        .filter(|field| primary_key.iter().all(|&i| field.ident.as_ref() != Some(&fields_name[i])))
+        .collect();
+
+    let changeset_name = format_ident!("{}Changeset", struct_name);
+
+    // whether the changeset struct and the function updating by the primary key with it are generated.
+    let has_changeset = args.ops.contains(Op::Update) && !primary_key.is_empty() && !changeset_fields.is_empty();
+
+    // the changeset struct.
+    let generated_changeset = if has_changeset {
+        let table_name_attr = version::table_name_attr(&table_module);
+        let vis = &item.vis;
+
+        let changeset_attrs: Vec<Vec<&Attribute>> = changeset_fields.iter().map(|field| field::derive_attrs(field)).collect();
+        let changeset_vis: Vec<&Visibility> = changeset_fields.iter().map(|field| &field.vis).collect();
+        let changeset_names: Vec<&Option<Ident>> = changeset_fields.iter().map(|field| &field.ident).collect();
+        let changeset_types: Vec<&Type> = changeset_fields.iter().map(|field| &field.ty).collect();
+        let primary_key_names: Vec<&Ident> = primary_key.iter().map(|&i| &fields_name[i]).collect();
+        let primary_key_attr = version::primary_key_attr(&primary_key_names);
+
+        let doc_changeset = format!(
+            "Changes to a [`{}`]. Only the fields which are `Some` are updated, a nullable field is set to `NULL` with `Some(None)`.",
+            struct_name
+        );
 
-            fn_names_get2.push(format_ident!("get_by_{}", field));
+        quote! {
+            #[doc = #doc_changeset]
+            #[derive(diesel::AsChangeset, Default)]
+            #table_name_attr
+            #primary_key_attr
+            #vis struct #changeset_name {
+                #(
+                    #(#changeset_attrs)*
+                    #changeset_vis #changeset_names: Option<#changeset_types>,
+                )*
+            }
 
-            params_names_for_delete_get2.push(field.clone());
-            
-            params_for_delete_get2.push(format_ident!("query_{}", field));
-            
-            param_types_for_delete_get2.push(fields_type[i].clone());
+            impl #changeset_name {
+                /// Whether no field is `Some`, so that the changeset changes nothing.
+                pub fn is_empty(&self) -> bool {
+                    #(self.#changeset_names.is_none())&&*
+                }
+            }
         }
+    } else {
+        quote! {}
+    };
 
-        let mut doc_title_get = Vec::new();
-        let mut doc_2_get = Vec::new();
-
-        let mut doc_title_get2 = Vec::new();
-        let mut doc_2_get2 = Vec::new();
+    let page_name = format_ident!("{}Page", struct_name);
 
-        let mut doc_title_update = Vec::new();
-        let mut doc_2_update = Vec::new();
-        let mut doc_3_update = Vec::new();
+    // the struct returned by the paginated functions.
+    let generated_page = if args.ops.contains(Op::GetAll) || args.ops.contains(Op::GetBy) {
+        let vis = &item.vis;
 
-        let doc_title_insert = format!("Insert a new [`{}`]", struct_name);
-        let doc_2_insert = "- The second parameter is the new value for inserting.".to_string();
-        let doc_3_insert = format!("*NOTE:* The second argument must be a [`New{}`]. It should live where the struct [`{}`] lives.", struct_name, struct_name);
+        let doc_page = format!("A page of [`{}`], returned by the `*_paginated` functions.", struct_name);
 
-        let mut doc_title_delete = Vec::new();
-        let mut doc_2_delete = Vec::new();
+        quote! {
+            #[doc = #doc_page]
+            #vis struct #page_name {
+                /// The rows in the page.
+                pub items: Vec<#struct_name>,
+                /// The number of the rows in all the pages.
+                pub total: i64,
+                /// The number of the page, the first page is 1. It is the page which was loaded, e.g. 1 for a page of 0.
+                pub page: i64,
+                /// The maximum number of rows in a page, at least 1.
+                pub per_page: i64,
+            }
 
-        for i in 0..fields.len() {
-            doc_title_get.push(format!(
-                "Get {}s by filtering `{}`",
-                fields[i], param_names_for_get[i]
-            ));
+            impl #page_name {
+                /// The number of pages, the last page may have fewer rows.
+                pub fn total_pages(&self) -> i64 {
+                    if self.per_page <= 0 {
+                        0
+                    } else {
+                        // without `total + per_page - 1`, which overflows for a large `per_page`.
+                        self.total / self.per_page + (self.total % self.per_page != 0) as i64
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // fields the rows can be ordered by, with their variants in the column enum and their names in the database.
+    let order_fields: Vec<usize> = (0..fields_name.len()).filter(|&i| !fields_attrs[i].skip).collect();
+    let order_variants: Vec<Ident> = order_fields
+        .iter()
+        .map(|&i| format_ident!("{}", naming::to_camel_case(&fields_name[i].unraw().to_string())))
+        .collect();
+    let order_names: Vec<String> = order_fields.iter().map(|&i| fields_name[i].unraw().to_string()).collect();
+
+    let column_name = format_ident!("{}Column", struct_name);
+    let direction_name = format_ident!("{}Direction", struct_name);
+
+    // whether the column and direction enums and the ordered functions are generated.
+    let has_order = (args.ops.contains(Op::GetAll) || args.ops.contains(Op::GetBy)) && !order_fields.is_empty();
+
+    // the enums of the columns and the directions to order the rows by.
+    let generated_order = if has_order {
+        let vis = &item.vis;
+
+        let doc_column = format!(
+            "A column of [`{}`] to order the rows by. It is parsed from and displayed as the name of the column.",
+            struct_name
+        );
+        let doc_direction = format!(
+            "The direction to order the rows of [`{}`] in. It is parsed from `asc` or `desc`.",
+            struct_name
+        );
+        let doc_variants: Vec<String> = order_names.iter().map(|name| format!("`{}`", name)).collect();
+        let expected = format!("expected one of {}", doc_variants.join(", "));
 
-            doc_2_get.push(format!(
-                "- The second argument is the `{}` by which you get the `Vec<{}>` of [`{}`]",
-                param_names_for_get[i], fields[i], struct_name
-            ));
+        quote! {
+            #[doc = #doc_column]
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+            #vis enum #column_name {
+                #(
+                    #[doc = #doc_variants]
+                    #order_variants,
+                )*
+            }
 
-            doc_title_get2.push(format!(
-                "Get [`{}`] by filtering `{}`",
-                struct_name, fields[i]
-            ));
+            impl std::str::FromStr for #column_name {
+                type Err = String;
 
-            doc_2_get2.push(format!(
-                "- The second argument is the `{}` by which you get the `Vec<{}>`",
-                fields[i], struct_name
-            ));
+                fn from_str(name: &str) -> Result<Self, Self::Err> {
+                    match name {
+                        #(#order_names => Ok(#column_name::#order_variants),)*
+                        _ => Err(format!("unknown column `{}`, {}", name, #expected)),
+                    }
+                }
+            }
 
-            doc_title_update.push(format!(
-                "Update {}s by `{}`",
-                fields[i], param_names_for_get[i]
-            ));
+            impl std::fmt::Display for #column_name {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    let name = match self {
+                        #(#column_name::#order_variants => #order_names,)*
+                    };
 
-            doc_2_update.push(format!(
-                "- The second argument is the `{}` by which you update [`{}`]",
-                param_names_for_get[i], struct_name
-            ));
+                    f.write_str(name)
+                }
+            }
 
-            doc_3_update.push(format!("- The third argument is the new `{}`", fields[i]));
+            #[doc = #doc_direction]
+            #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+            #vis enum #direction_name {
+                /// Ascending order, the smallest value first.
+                #[default]
+                Asc,
+                /// Descending order, the largest value first.
+                Desc,
+            }
 
-            doc_title_delete.push(format!(
-                "Delete [`{}`] by filtering `{}`",
-                struct_name, fields[i]
-            ));
+            impl std::str::FromStr for #direction_name {
+                type Err = String;
 
-            doc_2_delete.push(format!(
-                "- The second argument is the `{}` by which you delete [`{}`]",
-                fields[i], struct_name
-            ));
+                fn from_str(name: &str) -> Result<Self, Self::Err> {
+                    if name.eq_ignore_ascii_case("asc") {
+                        Ok(#direction_name::Asc)
+                    } else if name.eq_ignore_ascii_case("desc") {
+                        Ok(#direction_name::Desc)
+                    } else {
+                        Err(format!("unknown direction `{}`, expected `asc` or `desc`", name))
+                    }
+                }
+            }
 
+            impl std::fmt::Display for #direction_name {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(match self {
+                        #direction_name::Asc => "asc",
+                        #direction_name::Desc => "desc",
+                    })
+                }
+            }
         }
-        
+    } else {
+        quote! {}
+    };
+
+    let cursor_page_name = format_ident!("{}CursorPage", struct_name);
+
+    // the struct returned by the `get_after_*` and `get_before_*` functions.
+    let generated_cursor_page = if args.ops.contains(Op::GetAll) && !cursor_fields.is_empty() {
+        let vis = &item.vis;
+
+        let doc_cursor_page = format!(
+            "Rows of [`{}`] after or before a cursor, returned by the `get_after_*` and `get_before_*` functions.",
+            struct_name
+        );
+
+        // the cursor is sent to the client and given back in the next request.
+        let derive_serde = if cfg!(feature = "serde") {
+            quote! { #[derive(serde::Serialize, serde::Deserialize)] }
+        } else {
+            quote! {}
+        };
+
+        let cursors = cursor_fields.iter().zip(&cursor_keys).zip(&cursor_names).map(|((&i, keys), cursor_name)| {
+            let doc_cursor = format!(
+                "The position of a [`{}`] in the order of `{}`, returned in `next` by `get_after_{}` and `get_before_{}`. It is opaque, give it back as the cursor to get the following rows.",
+                struct_name, fields_fn_name[i], fields_fn_name[i], fields_fn_name[i]
+            );
+            let key_names = keys.iter().map(|&j| &fields_name[j]);
+            let key_types = keys.iter().map(|&j| &fields_type[j]);
+
+            quote! {
+                #[doc = #doc_cursor]
+                #[derive(Clone, Debug, PartialEq)]
+                #derive_serde
+                #vis struct #cursor_name {
+                    #(#key_names: #key_types,)*
+                }
+            }
+        });
 
         quote! {
-            #input
-
-            /// Functions for getting data from database
-            /// 
-            /// # Example
-            /// 
-            /// If you have a struct like this:
-            /// 
-            /// ```rust
-            /// #[diesel_ease(PgConnection)]
-            /// #[derive(Queryable, Clone, Debug, PartialEq)]
-            /// struct User {
-            ///    id: i32,
-            ///    name: String,
-            /// }
-            /// ```
-            /// 
-            /// Then you will get functions for getting `name` by `id` and `id` by `name`.
-            /// 
-            /// ```rust
-            /// const USER_ID: i32 = 19;
-            /// 
-            /// let connection = establish_connection();
-            /// 
-            /// // get the name of the User
-            /// let name = User::get_names_by_id(&connection, &USER_ID).unwrap();
-            /// 
-            /// // get the id of the User
-            /// let id = User::get_ids_by_name(&connection, &name[0]).unwrap();
-            /// 
-            /// assert_eq!(id[0], USER_ID);
-            /// 
-            /// // You can also get the User by id or name
-            /// let user: Vec<User> = User::get_by_id(&connection, &USER_ID).unwrap();
-            /// ```
-            /// 
-            impl #struct_name {
-                #(           
-                    #[doc = #doc_title_get]
-                    #[doc = ""]                     
-                    #[doc = "# Arguments"]
-                    #[doc = ""]
-                    #[doc = #doc_2_get]
-                    #[doc = ""]                     
-                    // get functions                           
-                    pub fn #fn_names_get(connection: &mut #connection_type, #params_for_get: &#param_types_for_get) ->
-                    diesel::result::QueryResult<Vec<#fn_return_types>> {
-                        use crate::schema::#struct_module_name::dsl::*;
-                        use diesel::prelude::*;
+            #[doc = #doc_cursor_page]
+            #vis struct #cursor_page_name<C> {
+                /// The rows, in the order of the cursor field.
+                pub items: Vec<#struct_name>,
+                /// The cursor of the following rows, give it to the same function to get them. `None` if there are no more rows.
+                pub next: Option<C>,
+            }
 
-                        let results = #struct_module_name
-                            .filter(#param_names_for_get.eq(#params_for_get))
-                            .load::<#struct_name>(connection);
+            #(#cursors)*
+        }
+    } else {
+        quote! {}
+    };
 
-                        let results = match results {
-                            Ok(vec_of_model) => {
-                                let results = vec_of_model.iter().map(|model| model.#fields.clone()).collect();
-                                
-                                Ok(results)
-                            }
-                            Err(error) => Err(error)
-                        };
+    // the rows are paginated in the order of the primary key, so that the pages do not overlap. Without a primary key
+    // they are ordered by all the columns, the rows with the same values are the same anyway.
+    let keys_for_page: Vec<usize> = if primary_key.is_empty() { (0..fields_name.len()).collect() } else { primary_key.clone() };
 
-                        results
-                    }
+    let order_for_page = match keys_for_page.as_slice() {
+        [i] => {
+            let field = &fields_name[*i];
 
-                    #[doc = #doc_title_get2]
-                    #[doc = ""]
-                    #[doc = "# Arguments"]
-                    #[doc = ""]
-                    #[doc = #doc_2_get2]
-                    #[doc = ""]
-                    // get2 functions                
-                    pub fn #fn_names_get2(connection: &mut #connection_type, #params_for_delete_get2: &#param_types_for_delete_get2) -> diesel::result::QueryResult<Vec<#struct_name>> {
-                        use crate::schema::#struct_module_name::dsl::*;
-                        use diesel::prelude::*;
+            quote! { #table_module::#field }
+        }
+        keys => {
+            let fields = keys.iter().map(|&i| &fields_name[i]);
 
-                        let results = #struct_module_name
-                            .filter(#params_names_for_delete_get2.eq(#params_for_delete_get2))
-                            .load::<#struct_name>(connection);
+            quote! { (#(#table_module::#fields),*) }
+        }
+    };
+
+    // `query()` and `filter_by_{field}`, returning boxed queries which can be extended before they are loaded. They do
+    // not take a connection, so they are only generated for the connection given to the macro, in generic mode
+    // generic over the backend.
+    let generated_builders = if args.ops.contains(Op::Query) {
+        let param_ref = connection.param_ref();
+
+        let (backend, lifetime, filter_generics, query_generics) = match &connection {
+            Connection::Type(path) => (quote! { <#path as diesel::Connection>::Backend }, quote! { '_ }, quote! {}, quote! {}),
+            _ => (quote! { DB }, quote! { 'q }, quote! { <'q, DB> }, quote! { <DB> }),
+        };
+
+        // the bound boxing the `query` type in generic mode.
+        let boxed_bound = |query_type: proc_macro2::TokenStream, lifetime: proc_macro2::TokenStream| match &connection {
+            Connection::Type(_) => quote! {},
+            _ => quote! {
+                #query_type: diesel::query_dsl::methods::BoxedDsl<#lifetime, DB, Output = #table_module::BoxedQuery<#lifetime, DB>>
+            },
+        };
+
+        let where_for = |bounds: &[proc_macro2::TokenStream]| match &connection {
+            Connection::Type(_) => quote! {},
+            _ => quote! {
+                where
+                    DB: diesel::backend::Backend,
+                    #(#bounds,)*
+            },
+        };
+
+        let where_for_query = where_for(&[boxed_bound(table_type.clone(), quote! { 'static })]);
+
+        let mut fn_names_filter: Vec<Ident> = Vec::new();
+        let mut params_for_filter: Vec<Ident> = Vec::new();
+        let mut param_types_for_filter: Vec<Type> = Vec::new();
+        let mut statements_for_filter = Vec::new();
+        let mut wheres_for_filter = Vec::new();
+        let mut doc_title_filter = Vec::new();
+        let mut doc_2_filter = Vec::new();
 
-                        results
-                    }
-                )*
+        for (i, field) in fields_name.iter().enumerate() {
+            if fields_attrs[i].skip || fields_attrs[i].no_filter {
+                continue;
             }
 
-            /// Functions for updating data from database
-            /// 
-            /// # Example
-            /// 
-            /// If you have a struct like this:
-            /// 
-            /// ```rust
-            /// #[diesel_ease(PgConnection)]
-            /// #[derive(Queryable, Clone, Debug, PartialEq)]
-            /// struct User {
-            ///    id: i32,
-            ///    name: String,
-            /// }
-            /// ```
-            /// 
-            /// Then you will get functions for updating `name` by `id` and `id` by `name`.
-            /// 
-            /// ```rust
-            /// let connection = establish_connection();
-            /// 
-            /// // get the old user
-            /// let old_user: Vec<User> = User::get_by_name(&connection, &"John".into()).unwrap();
-            /// 
-            /// // update the user
-            /// let new_updated_user: User =
-            ///     User::update_names_by_id(&connection, &(old_user[0].id), &String::from("Johny Depth"))
-            ///         .unwrap();
-            /// 
-            /// println!("OLD user: {:?}", old_user[0]);
-            /// println!("NEW user: {:?}", new_updated_user);
-            /// 
-            /// assert_ne!(old_user[0].name, new_updated_user.name);
-            /// ```
-            // Update functions
+            let query_param = format_ident!("query_{}", field);
+
+            let (statement, bounds) = filter_by(&table_module, field, &fields_type[i], &query_param, &param_ref, |predicate, predicate_type| {
+                (
+                    quote! { #table_module::table.filter(#predicate).into_boxed() },
+                    boxed_bound(quote! { diesel::dsl::Filter<#table_type, #predicate_type> }, quote! { 'q }),
+                )
+            });
+
+            fn_names_filter.push(format_ident!("filter_by_{}", fields_fn_name[i]));
+            params_for_filter.push(query_param);
+            param_types_for_filter.push(fields_type[i].clone());
+            statements_for_filter.push(statement);
+            wheres_for_filter.push(where_for(&bounds));
+
+            doc_title_filter.push(format!("Query of the [`{}`] filtered by `{}`, which can be extended before it is loaded", struct_name, field));
+            doc_2_filter.push(format!("- The argument is the `{}` by which you filter the [`{}`]", field, struct_name));
+        }
+
+        quote! {
             impl #struct_name {
+                /// Query of all the rows, which can be extended with more filters, an order or a limit before it is loaded
+                ///
+                /// # Example
+                ///
+                /// ```rust,ignore
+                /// let users: Vec<User> = User::query().limit(10).load(&mut connection)?;
+                /// ```
+                pub fn query #query_generics () -> #table_module::BoxedQuery<'static, #backend> #where_for_query {
+                    use diesel::prelude::*;
+                    #table_module::table.into_boxed()
+                }
+
                 #(
-                    #[doc = #doc_title_update]
+                    #[doc = #doc_title_filter]
                     #[doc = ""]
                     #[doc = "# Arguments"]
                     #[doc = ""]
-                    #[doc = #doc_2_update]
-                    #[doc = ""]
-                    #[doc = #doc_2_update]
-                    #[doc = ""]
-                    #[doc = #doc_3_update]
+                    #[doc = #doc_2_filter]
                     #[doc = ""]
-                    // update functions
-                    pub fn #fn_names_update(connection: &mut #connection_type, #params_for_get: &#param_types_for_get, #new_fields_params: &#new_fields_types) -> diesel::result::QueryResult<#struct_name> {
-                        use crate::schema::#struct_module_name::dsl::*;
+                    pub fn #fn_names_filter #filter_generics (#params_for_filter: #param_ref #param_types_for_filter) -> #table_module::BoxedQuery<#lifetime, #backend> #wheres_for_filter {
                         use diesel::prelude::*;
-
-                        diesel::update(#struct_module_name.filter(#param_names_for_get.eq(#params_for_get)))
-                            .set(#new_fields_names.eq(#new_fields_params))
-                            .get_result::<#struct_name>(connection)
+                        #statements_for_filter
                     }
                 )*
             }
+        }
+    } else {
+        quote! {}
+    };
 
-            /// Functions for inserting data into database
-            /// 
-            /// # Example
-            /// 
-            /// If you have structs like this:
-            /// 
-            /// ```rust
-            /// #[diesel_ease(PgConnection)]
-            /// #[derive(Queryable, Clone, Debug, PartialEq)]
-            /// struct User {
-            ///   id: i32,
-            ///   name: String,
-            /// }
-            /// 
-            /// #[derive(Insertable)]
-            /// #[table_name = "users"]
-            /// struct NewUser {
-            ///   name: String,
-            /// }
-            ///  
-            /// ```            
-            /// Then you use the `insert` method to insert a new user.
-            /// 
-            /// ```rust
-            /// let connection = establish_connection();
-            /// 
-            /// let new_user = NewUser {
-            ///     name: "Sean".into(),
-            /// };
-            /// 
-            /// let inserted_user = User::insert(&connection, new_user).unwrap();
-            /// 
-            /// println!("New User: {:?}", inserted_user);            
-            /// ```
-            impl #struct_name {
+    let filter_name = format_ident!("{}Filter", struct_name);
 
-                #[doc = #doc_title_insert]
-                #[doc = ""]
-                #[doc = "# Arguments"]
-                #[doc = ""]
-                #[doc = #doc_2_insert]
-                #[doc = ""]
-                #[doc = #doc_3_insert]
-                // insert function
-                pub fn insert(connection: &mut #connection_type, #params_for_insert: #param_types_for_insert) -> diesel::result::QueryResult<#struct_name> {
-                    use diesel::prelude::*;
+    // the fields of the filter struct: the field, the name in the filter and the comparison, one of `eq`, `gt`, `lt`
+    // and `like`. Every field can be compared for equality, numbers and times also with `_gt` and `_lt`, text with
+    // `_like`.
+    let mut filter_fields: Vec<(usize, Ident, &str)> = Vec::new();
 
-                    diesel::insert_into(crate::schema::#struct_module_name::table)
-                        .values(#params_for_insert)
-                        .get_result::<#struct_name>(connection)
-                    
-                }
-            }
+    for (i, field) in fields_name.iter().enumerate() {
+        if fields_attrs[i].skip || fields_attrs[i].no_filter {
+            continue;
+        }
 
-            /// Functions for deleting data from database
-            /// 
-            /// # Example
-            /// 
-            /// If you have structs like this:
-            /// 
-            /// ```rust
-            /// #[diesel_ease(PgConnection)]
-            /// #[derive(Queryable, Clone, Debug, PartialEq)]
-            /// struct User {
-            ///  id: i32,
-            ///  name: String,
-            /// }
-            /// ```
-            /// 
-            /// Then you can delete `User` by `id` or `name`.
-            /// 
-            /// ```rust
-            /// let connection = establish_connection();
-            /// 
-            /// // delete by id
-            /// let deleted_user: usize = User::delete_by_id(&connection, &6).unwrap();
-            /// 
-            /// println!("Deleted {} users by id", deleted_user);
-            /// 
-            /// // delete by name
-            /// let deleted_user = User::delete_by_name(&connection, &String::from("Python Lover")).unwrap();
-            /// 
-            /// println!("Deleted {} users by name", deleted_user);
-            /// ```
-            ///         
-            impl #struct_name {
-                #(
-                    #[doc = #doc_title_delete]
-                    #[doc = ""]
-                    #[doc = "# Arguments"]
-                    #[doc = ""]
-                    #[doc = #doc_2_delete]
-                    #[doc = ""]
-                    // delete functions
-                    pub fn #fn_names_delete(connection: &mut #connection_type, #params_for_delete_get2: &#param_types_for_delete_get2) -> diesel::result::QueryResult<usize> {
-                        use crate::schema::#struct_module_name::dsl::*;
-                        use diesel::prelude::*;
+        let ty = option_inner(&fields_type[i]).unwrap_or(&fields_type[i]);
 
-                        let num_deleted = diesel::delete(#struct_module_name.filter(#params_names_for_delete_get2.eq(#params_for_delete_get2)))
-                            .execute(connection);
+        filter_fields.push((i, field.clone(), "eq"));
 
-                        num_deleted
-                    }
+        if is_ordered(ty) {
+            filter_fields.push((i, format_ident!("{}_gt", field.unraw()), "gt"));
+            filter_fields.push((i, format_ident!("{}_lt", field.unraw()), "lt"));
+        }
+
+        if is_text(ty) {
+            filter_fields.push((i, format_ident!("{}_like", field.unraw()), "like"));
+        }
+    }
+
+    // the filter struct of `find_where`, the fields which are `Some` are compared.
+    let generated_filter = if args.ops.contains(Op::Query) && !filter_fields.is_empty() {
+        let vis = &item.vis;
+
+        let filter_names: Vec<&Ident> = filter_fields.iter().map(|(_, name, _)| name).collect();
+        let mut filter_types = Vec::new();
+        let mut doc_filter_fields = Vec::new();
+
+        for (i, _, comparison) in &filter_fields {
+            let field = &fields_name[*i];
+            let ty = &fields_type[*i];
+            let inner = option_inner(ty).unwrap_or(ty);
+
+            // equality keeps `Option` of a nullable field, so that `Some(None)` matches `NULL`.
+            filter_types.push(match *comparison {
+                "eq" => quote! { Option<#ty> },
+                _ => quote! { Option<#inner> },
+            });
+
+            doc_filter_fields.push(match *comparison {
+                "eq" if option_inner(ty).is_some() => format!("`{}` is equal to the value, or `NULL` with `Some(None)`", field),
+                "eq" => format!("`{}` is equal to the value", field),
+                "gt" => format!("`{}` is greater than the value", field),
+                "lt" => format!("`{}` is less than the value", field),
+                _ => format!("`{}` matches the `LIKE` pattern", field),
+            });
+        }
+
+        let doc_filter = format!(
+            "A filter of [`{}`] for `find_where`. The rows match when all the fields which are `Some` match.",
+            struct_name
+        );
+
+        // the filter is usually deserialized from the query string of a request.
+        let derive_deserialize = if cfg!(feature = "serde") {
+            quote! { #[derive(serde::Deserialize)] }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            #[doc = #doc_filter]
+            #[derive(Clone, Debug, Default, PartialEq)]
+            #derive_deserialize
+            #vis struct #filter_name {
+                #(
+                    #[doc = #doc_filter_fields]
+                    pub #filter_names: #filter_types,
                 )*
             }
-            
-            impl #struct_name {
-
-                /// Get all data from database
-                pub fn get_all(connection: &mut #connection_type) -> diesel::result::QueryResult<Vec<#struct_name>> {
-                    use diesel::prelude::*;
+        }
+    } else {
+        quote! {}
+    };
+
+    // the functions taking a connection, for the connection given to the macro or an async connection.
+    let functions = |connection: &Connection| -> Result<proc_macro2::TokenStream> {
+        // generic parameters, connection type and reference to the query parameters of the functions.
+        let connection_generics = connection.generics();
+        let connection_type = connection.ty();
+        let param_ref = connection.param_ref();
+
+        // whether the backend returns the changed rows with `RETURNING`. Otherwise they are loaded again.
+        let returning = connection.supports_returning(args.backend);
+
+        // `async` for an async connection, and the imports of the function bodies.
+        let asyncness = connection.asyncness();
+        let prelude = connection.prelude();
+
+        // names of the functions which are not generated per field.
+        let fn_name_insert = connection.fn_name(format_ident!("insert"));
+        let fn_name_find = connection.fn_name(format_ident!("find"));
+        let fn_name_find_or_err = connection.fn_name(format_ident!("find_or_err"));
+        let fn_name_get_all = connection.fn_name(format_ident!("get_all"));
+        let fn_name_delete_all = connection.fn_name(format_ident!("delete_all"));
+        let fn_name_get_all_paginated = connection.fn_name(format_ident!("get_all_paginated"));
+        let fn_name_get_all_ordered = connection.fn_name(format_ident!("get_all_ordered"));
+        let fn_name_find_where = connection.fn_name(format_ident!("find_where"));
+
+        // queries whose filters or order are only known at runtime are boxed, so that they have a single type.
+        let backend = connection.backend();
+
+        // the async functions take their parameters by value, the values are moved into the query, which is
+        // `'static`. A query borrowing from the future would make it not `Send`.
+        let boxed_lifetime = match connection {
+            Connection::Type(_) => quote! { '_ },
+            Connection::Generic => quote! { 'q },
+            Connection::Async => quote! { 'static },
+        };
+
+        let boxed_type = quote! { #table_module::BoxedQuery<#boxed_lifetime, #backend> };
+
+        // diesel 1 only boxes the table without a filter as `'static`, the query is shortened to `'q`.
+        let boxed_bounds = vec![
+            quote! { #backend: 'q },
+            quote! {
+                #table_type: diesel::query_dsl::methods::BoxedDsl<'static, #backend, Output = #table_module::BoxedQuery<'static, #backend>>
+            },
+        ];
+
+        // the async functions loading a boxed query return the future of diesel-async instead of being an `async fn`.
+        // A future holding the boxed query would not be `Send` in generic code.
+        let (boxed_connection_param, boxed_return_type) = match connection {
+            Connection::Async => (
+                quote! { &'q mut #connection_type },
+                quote! { impl std::future::Future<Output = diesel::result::QueryResult<Vec<#struct_name>>> + Send + 'q },
+            ),
+            _ => (
+                quote! { &mut #connection_type },
+                quote! { diesel::result::QueryResult<Vec<#struct_name>> },
+            ),
+        };
+
+        // statement loading the rows of the boxed `query`, it adds the bounds it needs to `bounds`.
+        let load_boxed = |bounds: &mut Vec<proc_macro2::TokenStream>| {
+            // with diesel 2 the rows are selected with `Selectable`, which a boxed query needs a bound for.
+            if version::DIESEL2 {
+                bounds.push(quote! {
+                    #boxed_type: diesel::query_dsl::methods::SelectDsl<diesel::dsl::AsSelect<#struct_name, #backend>>
+                });
+            }
 
-                    crate::schema::#struct_module_name::table.load::<#struct_name>(connection)
-                }
+            let (query, query_type) = connection.select_model(struct_name, quote! { query }, boxed_type.clone());
 
-                /// Delete all data from database
-                pub fn delete_all(connection: &mut #connection_type) -> diesel::result::QueryResult<usize> {
-                    use diesel::prelude::*;
+            bounds.push(connection.load_bound(query_type, quote! { Self }));
 
-                    diesel::delete(crate::schema::#struct_module_name::table).execute(connection)
+            match connection {
+                Connection::Async => quote! { diesel_async::RunQueryDsl::load(#query, connection) },
+                _ => connection.run(query, quote! { load::<#struct_name> }),
+            }
+        };
+
+        // statement loading the rows ordered by `column` in `direction`, with the bounds it needs. `filter` filters the
+        // boxed table in `query`, then each column only adds an `order_by` to the same query.
+        let order_by = |filter: proc_macro2::TokenStream, filter_bounds: Vec<proc_macro2::TokenStream>| {
+            let mut arms = Vec::new();
+            let mut bounds = boxed_bounds.clone();
+
+            bounds.extend(filter_bounds);
+
+            for (&i, variant) in order_fields.iter().zip(&order_variants) {
+                let field = &fields_name[i];
+                let column = quote! { #table_module::#field };
+
+                let directions = [
+                    (quote! { Asc }, quote! { asc }, quote! { diesel::dsl::Asc }),
+                    (quote! { Desc }, quote! { desc }, quote! { diesel::dsl::Desc }),
+                ];
+
+                for (direction, order, order_type) in directions {
+                    arms.push(quote! { (#column_name::#variant, #direction_name::#direction) => query.order_by(#column.#order()) });
+                    bounds.push(quote! {
+                        #boxed_type: diesel::query_dsl::methods::OrderDsl<#order_type<#column>, Output = #boxed_type>
+                    });
                 }
             }
-            
-        
 
-        }
-        .into()
-    } else {
-        panic!("Only structs with named fields are supported.");
+            let load = load_boxed(&mut bounds);
+
+            let statement = quote! {{
+                let mut query: #boxed_type = #table_module::table.into_boxed();
+
+                #filter
+
+                query = match (column, direction) {
+                    #(#arms,)*
+                };
+
+                #load
+            }};
+
+            (statement, bounds)
+        };
+
+        // statement loading a page of the rows of `query` and counting all of them, with the bounds it needs.
+        // `page` and `per_page` are parameters of the function, e.g. from a query string, so they are clamped to 1 and
+        // the offset saturates instead of overflowing.
+        let paginate = |query: proc_macro2::TokenStream, query_type: proc_macro2::TokenStream| {
+            let (selected, selected_type) = connection.select_model(
+                struct_name,
+                quote! { query.order_by(#order_for_page) },
+                quote! { diesel::dsl::Order<#query_type, #order_for_page> },
+            );
+
+            let count = connection.run(quote! { query.clone().count() }, quote! { get_result::<i64> });
+            let load = connection.run(
+                quote! { #selected.limit(per_page).offset((page - 1).saturating_mul(per_page)) },
+                quote! { load::<#struct_name> },
+            );
+
+            let statement = quote! {{
+                let page = page.max(1);
+                let per_page = per_page.max(1);
+
+                let query = #query;
+
+                let total = #count?;
+                let items = #load?;
+
+                Ok(#page_name { items, total, page, per_page })
+            }};
+
+            let count_bound = connection.load_bound(
+                quote! { diesel::dsl::Select<#query_type, diesel::dsl::count_star> },
+                quote! { i64 },
+            );
+            let load_bound = connection.load_bound(
+                quote! { diesel::dsl::Offset<diesel::dsl::Limit<#selected_type>> },
+                quote! { Self },
+            );
+
+            (statement, quote! { #count_bound, #load_bound })
+        };
+
+        // name of functions for get functions
+        let mut fn_names_get: Vec<Ident> = Vec::new();
+
+        // name of functions for get2 functions
+        let mut fn_names_get2: Vec<Ident> = Vec::new();
+
+        // name of functions for get functions that filter by a unique field, they return one value.
+        let mut fn_names_get_single: Vec<Ident> = Vec::new();
+
+        // name of functions for get_one functions, they get one row by a unique field.
+        let mut fn_names_get_one: Vec<Ident> = Vec::new();
+
+        // name of functions for update functions
+        let mut fn_names_update = Vec::new();
+
+        // name of functions for update functions that return the number of updated rows.
+        let mut fn_names_update_count = Vec::new();
+
+        // name of functions for delete functions
+        let mut fn_names_delete = Vec::new();
+
+        // return type of functions
+        let mut fn_return_types = Vec::new();
+
+        // parameters of functions. This variable contains "query_{param}"
+        let mut params_for_get = Vec::new();
+
+        // types of the parameters.
+        let mut param_types_for_get= Vec::new();
+
+        // parameters and their types for update functions.
+        let mut params_for_update = Vec::new();
+        let mut param_types_for_update = Vec::new();
+
+        // filter + load expressions for get functions.
+        let mut loads_for_get = Vec::new();
+
+        // filter + load expressions for get2 functions.
+        let mut loads_for_get2 = Vec::new();
+
+        // names, statements and where clauses of the paginated get2 functions.
+        let mut fn_names_get2_paginated: Vec<Ident> = Vec::new();
+        let mut loads_for_get2_paginated = Vec::new();
+        let mut wheres_for_get2_paginated = Vec::new();
+
+        // names, statements and where clauses of the ordered get2 functions.
+        let mut fn_names_get2_ordered: Vec<Ident> = Vec::new();
+        let mut loads_for_get2_ordered = Vec::new();
+        let mut wheres_for_get2_ordered = Vec::new();
+
+        // parameters, their types, return types and filter + load expressions for single get functions.
+        let mut params_for_get_single = Vec::new();
+        let mut param_types_for_get_single = Vec::new();
+        let mut fn_return_types_single = Vec::new();
+        let mut loads_for_get_single = Vec::new();
+
+        // parameters, their types and filter + load expressions for get_one functions.
+        let mut params_for_get_one = Vec::new();
+        let mut param_types_for_get_one = Vec::new();
+        let mut loads_for_get_one = Vec::new();
+
+        // update statements for update functions.
+        let mut statements_for_update = Vec::new();
+        let mut statements_for_update_count = Vec::new();
+
+        // delete statements for delete functions.
+        let mut statements_for_delete = Vec::new();
+
+        // where clauses of the functions. These are only non-empty for a generic connection.
+        let mut wheres_for_get = Vec::new();
+        let mut wheres_for_get2 = Vec::new();
+        let mut wheres_for_get_single = Vec::new();
+        let mut wheres_for_get_one = Vec::new();
+        let mut wheres_for_update = Vec::new();
+        let mut wheres_for_update_count = Vec::new();
+        let mut wheres_for_delete = Vec::new();
+
+        // parameters for get2 functions.
+        let mut params_for_get2 = vec![];
+
+        // parameter types for get2 functions.
+        let mut param_types_for_get2 = Vec::new();
+
+        // parameters for delete functions.
+        let mut params_for_delete = vec![];
+
+        // parameter types for delete functions.
+        let mut param_types_for_delete = Vec::new();
+
+        // parameter for update functions
+        let mut new_fields_params = Vec::new();
+
+        // parameter types for update functions
+        let mut new_fields_types = Vec::new();   
+
+        // parameter for insert functions
+        let params_for_insert = format_ident!("new_{}", struct_name_lower);
+
+        // parameter types for insert functions. Without `insertable = ...` it is any type that is `Insertable` into the table.
+        let param_types_for_insert = match &args.insertable {
+            Some(insertable) => quote! { #insertable },
+            None => quote! { T },
+        };
+
+        let mut doc_title_get = Vec::new();
+        let mut doc_2_get = Vec::new();
+
+        let mut doc_title_get2 = Vec::new();
+        let mut doc_2_get2 = Vec::new();
+        let mut doc_title_get2_paginated = Vec::new();
+        let mut doc_title_get2_ordered = Vec::new();
+
+        let doc_column = format!("- `column` is the [`{}`] to order the rows by", column_name);
+        let doc_direction = format!("- `direction` is the [`{}`] to order the rows in", direction_name);
+
+        let doc_order_for_page = if primary_key.is_empty() {
+            format!("all the columns of [`{}`], it has no primary key", struct_name)
+        } else {
+            format!("the primary key of [`{}`]", struct_name)
+        };
+        let doc_page = format!("- `page` is the number of the page, the first page is 1 and a lower number is the first page. The rows are ordered by {}", doc_order_for_page);
+        let doc_per_page = "- `per_page` is the maximum number of rows in the page, at least 1";
+
+        let mut doc_title_get_single = Vec::new();
+        let mut doc_2_get_single = Vec::new();
+
+        let mut doc_title_get_one = Vec::new();
+        let mut doc_2_get_one = Vec::new();
+
+        let mut doc_title_update = Vec::new();
+        let mut doc_2_update = Vec::new();
+        let mut doc_3_update = Vec::new();
+
+        let doc_title_insert = format!("Insert a new [`{}`]", struct_name);
+        let doc_2_insert = "- The second parameter is the new value for inserting.".to_string();
+        let doc_3_insert = match &args.insertable {
+            Some(insertable) => format!("*NOTE:* The second argument must be a `{}`.", quote!(#insertable).to_string().replace(' ', "")),
+            None => format!("*NOTE:* The second argument can be any value that is `Insertable` into the table of [`{}`], e.g. a `New{}`.", struct_name, struct_name),
+        };
+
+        let mut doc_title_delete = Vec::new();
+        let mut doc_2_delete = Vec::new();
+
+
+        /*
+            * `field2` is a variable through which we can get `field`.
+            * `i` is used to get the type of `field`. `i` is incremented in outer loop. That means `i` will be the same for `field`'s index.
+            * `j` is used to get the type of `field2`. `j` is incremented in inner loop. That means `j` will be the same for `field2`'s index.
+            * `#[ease(skip)]` fields are not used at all, `#[ease(no_filter)]` fields are never `field2`.
+            * `#[ease(immutable)]` fields are never updated, `#[ease(readonly)]` fields are not used by update and delete functions.
+
+
+        */
+
+        for (i, field) in fields_name.iter().enumerate() {
+            if fields_attrs[i].skip {
+                continue;
+            }
+
+            for (j, field2) in fields_name.iter().enumerate() {
+                if field != field2 && !fields_attrs[j].skip && !fields_attrs[j].no_filter {
+                    fn_names_get.push(connection.fn_name(format_ident!("get_{}", args.naming.name(&fields_fn_name[i], &fields_fn_name[j]))));
+
+                    fn_return_types.push(fields_type[i].clone());
+
+                    params_for_get.push(format_ident!("query_{}", field2));
+
+                    param_types_for_get.push(fields_type[j].clone());
+
+                    let query_param = format_ident!("query_{}", field2);
+                    let new_field_param = format_ident!("new_{}", field);
+                    let new_field_type = &fields_type[i];
+
+                    // only the column of `field` is selected, not the whole row.
+                    let (load, bounds) = filter_by(&table_module, field2, &fields_type[j], &query_param, &param_ref, |predicate, predicate_type| {
+                        (
+                            connection.run(
+                                quote! { #struct_module_name.filter(#predicate).select(#field) },
+                                quote! { load::<#new_field_type> },
+                            ),
+                            connection.load_bound(
+                                quote! { diesel::dsl::Select<diesel::dsl::Filter<#table_type, #predicate_type>, #table_module::#field> },
+                                quote! { #new_field_type },
+                            ),
+                        )
+                    });
+
+                    loads_for_get.push(load);
+                    wheres_for_get.push(connection.where_clause(&bounds));
+
+                    doc_title_get.push(format!("Get {}s by filtering `{}`", field, field2));
+
+                    doc_2_get.push(format!(
+                        "- The second argument is the `{}` by which you get the `Vec<{}>` of [`{}`]",
+                        field2, field, struct_name
+                    ));
+
+                    if fields_attrs[j].unique {
+                        fn_names_get_single.push(connection.fn_name(format_ident!("get_{}_by_{}", fields_fn_name[i], fields_fn_name[j])));
+
+                        fn_return_types_single.push(fields_type[i].clone());
+
+                        params_for_get_single.push(query_param.clone());
+
+                        param_types_for_get_single.push(fields_type[j].clone());
+
+                        let (load, bounds) = filter_by(&table_module, field2, &fields_type[j], &query_param, &param_ref, |predicate, predicate_type| {
+                            (
+                                {
+                                    let first = connection.run(
+                                        quote! { #struct_module_name.filter(#predicate).select(#field) },
+                                        quote! { first::<#new_field_type> },
+                                    );
+
+                                    quote! { #first.optional() }
+                                },
+                                connection.load_bound(
+                                    quote! {
+                                        diesel::dsl::Limit<
+                                            diesel::dsl::Select<diesel::dsl::Filter<#table_type, #predicate_type>, #table_module::#field>,
+                                        >
+                                    },
+                                    quote! { #new_field_type },
+                                ),
+                            )
+                        });
+
+                        loads_for_get_single.push(load);
+                        wheres_for_get_single.push(connection.where_clause(&bounds));
+
+                        doc_title_get_single.push(format!("Get the {} by the unique `{}`", field, field2));
+
+                        doc_2_get_single.push(format!(
+                            "- The second argument is the `{}` by which you get the `{}` of the [`{}`], or `None` if there is no such row",
+                            field2, field, struct_name
+                        ));
+                    }
+
+                    if fields_attrs[i].immutable || fields_attrs[i].readonly || fields_attrs[j].readonly {
+                        continue;
+                    }
+
+                    let update_name = args.naming.name(&fields_fn_name[i], &fields_fn_name[j]);
+
+                    fn_names_update.push(connection.fn_name(format_ident!("update_{}", update_name)));
+
+                    fn_names_update_count.push(connection.fn_name(format_ident!("update_{}_count", update_name)));
+
+                    params_for_update.push(query_param.clone());
+
+                    param_types_for_update.push(fields_type[j].clone());
+
+                    new_fields_params.push(new_field_param.clone());
+
+                    new_fields_types.push(new_field_type.clone());
+
+                    // type of the update statement, for the bounds of generic functions.
+                    let update_type = |predicate_type: proc_macro2::TokenStream| {
+                        quote! {
+                            diesel::dsl::Update<
+                                diesel::dsl::Filter<#table_type, #predicate_type>,
+                                diesel::dsl::Eq<#table_module::#field, #param_ref #new_field_type>,
+                            >
+                        }
+                    };
+
+                    let (statement, bounds) = filter_by(&table_module, field2, &fields_type[j], &query_param, &param_ref, |predicate, predicate_type| {
+                        if !returning {
+                            // `field2` is not updated, so the filter still matches the updated rows.
+                            let (query, _) = connection.select_model(
+                                struct_name,
+                                quote! { #struct_module_name.filter(#predicate) },
+                                quote! {},
+                            );
+
+                            let execute = connection.run(
+                                quote! { diesel::update(#struct_module_name.filter(#predicate)).set(#field.eq(#new_field_param)) },
+                                quote! { execute },
+                            );
+                            let load = connection.run(query, quote! { load::<#struct_name> });
+
+                            let statement = version::transaction(quote! {
+                                #execute?;
+
+                                #load
+                            });
+
+                            return (statement, quote! {});
+                        }
+
+                        (
+                            connection.run(
+                                quote! { diesel::update(#struct_module_name.filter(#predicate)).set(#field.eq(#new_field_param)) },
+                                quote! { get_results::<#struct_name> },
+                            ),
+                            connection.load_bound(update_type(predicate_type), quote! { Self }),
+                        )
+                    });
+
+                    statements_for_update.push(statement);
+                    wheres_for_update.push(connection.where_clause(&bounds));
+
+                    let (statement, bounds) = filter_by(&table_module, field2, &fields_type[j], &query_param, &param_ref, |predicate, predicate_type| {
+                        (
+                            connection.run(
+                                quote! { diesel::update(#struct_module_name.filter(#predicate)).set(#field.eq(#new_field_param)) },
+                                quote! { execute },
+                            ),
+                            connection.execute_bound(update_type(predicate_type)),
+                        )
+                    });
+
+                    statements_for_update_count.push(statement);
+                    wheres_for_update_count.push(connection.where_clause(&bounds));
+
+                    doc_title_update.push(format!("Update {}s by `{}`", field, field2));
+
+                    doc_2_update.push(format!(
+                        "- The second argument is the `{}` by which you update [`{}`]",
+                        field2, struct_name
+                    ));
+
+                    doc_3_update.push(format!("- The third argument is the new `{}`", field));
+                }
+
+            }
+
+            if fields_attrs[i].no_filter {
+                continue;
+            }
+
+            fn_names_get2.push(connection.fn_name(format_ident!("get_by_{}", fields_fn_name[i])));
+
+            params_for_get2.push(format_ident!("query_{}", field));
+
+            param_types_for_get2.push(fields_type[i].clone());
+
+            let query_param = format_ident!("query_{}", field);
+
+            let (load, bounds) = filter_by(&table_module, field, &fields_type[i], &query_param, &param_ref, |predicate, predicate_type| {
+                let (query, query_type) = connection.select_model(
+                    struct_name,
+                    quote! { #struct_module_name.filter(#predicate) },
+                    quote! { diesel::dsl::Filter<#table_type, #predicate_type> },
+                );
+
+                (
+                    connection.run(query, quote! { load::<#struct_name> }),
+                    connection.load_bound(query_type, quote! { Self }),
+                )
+            });
+
+            loads_for_get2.push(load);
+            wheres_for_get2.push(connection.where_clause(&bounds));
+
+            fn_names_get2_paginated.push(connection.fn_name(format_ident!("get_by_{}_paginated", fields_fn_name[i])));
+
+            let (load, bounds) = filter_by(&table_module, field, &fields_type[i], &query_param, &param_ref, |predicate, predicate_type| {
+                paginate(
+                    quote! { #table_module::table.filter(#predicate) },
+                    quote! { diesel::dsl::Filter<#table_type, #predicate_type> },
+                )
+            });
+
+            loads_for_get2_paginated.push(load);
+            wheres_for_get2_paginated.push(connection.where_clause(&bounds));
+
+            fn_names_get2_ordered.push(connection.fn_name(format_ident!("get_by_{}_ordered", fields_fn_name[i])));
+
+            let (filter, filter_bounds) = filter_by(&table_module, field, &fields_type[i], &query_param, &param_ref, |predicate, predicate_type| {
+                (
+                    quote! { query.filter(#predicate) },
+                    quote! { #boxed_type: diesel::query_dsl::methods::FilterDsl<#predicate_type, Output = #boxed_type> },
+                )
+            });
+            let (load, bounds) = order_by(quote! { query = #filter; }, filter_bounds);
+
+            loads_for_get2_ordered.push(load);
+            wheres_for_get2_ordered.push(connection.where_clause(&bounds));
+
+            doc_title_get2_paginated.push(format!("Get a page of the [`{}`] by filtering `{}`", struct_name, field));
+            doc_title_get2_ordered.push(format!("Get [`{}`] by filtering `{}`, ordered by a column", struct_name, field));
+
+            doc_title_get2.push(format!("Get [`{}`] by filtering `{}`", struct_name, field));
+
+            doc_2_get2.push(format!(
+                "- The second argument is the `{}` by which you get the `Vec<{}>`",
+                field, struct_name
+            ));
+
+            if fields_attrs[i].unique {
+                fn_names_get_one.push(connection.fn_name(format_ident!("get_one_by_{}", fields_fn_name[i])));
+
+                params_for_get_one.push(query_param.clone());
+
+                param_types_for_get_one.push(fields_type[i].clone());
+
+                let (load, bounds) = filter_by(&table_module, field, &fields_type[i], &query_param, &param_ref, |predicate, predicate_type| {
+                    let (query, query_type) = connection.select_model(
+                        struct_name,
+                        quote! { #struct_module_name.filter(#predicate) },
+                        quote! { diesel::dsl::Filter<#table_type, #predicate_type> },
+                    );
+
+                    (
+                        {
+                            let first = connection.run(query, quote! { first::<#struct_name> });
+
+                            quote! { #first.optional() }
+                        },
+                        connection.load_bound(quote! { diesel::dsl::Limit<#query_type> }, quote! { Self }),
+                    )
+                });
+
+                loads_for_get_one.push(load);
+                wheres_for_get_one.push(connection.where_clause(&bounds));
+
+                doc_title_get_one.push(format!("Get the [`{}`] by the unique `{}`", struct_name, field));
+
+                doc_2_get_one.push(format!(
+                    "- The second argument is the `{}` by which you get the [`{}`], or `None` if there is no such row",
+                    field, struct_name
+                ));
+            }
+
+            if fields_attrs[i].readonly {
+                continue;
+            }
+
+            fn_names_delete.push(connection.fn_name(format_ident!("delete_by_{}", fields_fn_name[i])));
+
+            params_for_delete.push(query_param.clone());
+
+            param_types_for_delete.push(fields_type[i].clone());
+
+            let (statement, bounds) = filter_by(&table_module, field, &fields_type[i], &query_param, &param_ref, |predicate, predicate_type| {
+                (
+                    connection.run(quote! { diesel::delete(#struct_module_name.filter(#predicate)) }, quote! { execute }),
+                    connection.execute_bound(quote! {
+                        diesel::query_builder::DeleteStatement<
+                            #table_type,
+                            <diesel::dsl::Filter<#table_type, #predicate_type> as diesel::query_builder::IntoUpdateTarget>::WhereClause,
+                        >
+                    }),
+                )
+            });
+
+            statements_for_delete.push(statement);
+            wheres_for_delete.push(connection.where_clause(&bounds));
+
+            doc_title_delete.push(format!("Delete [`{}`] by filtering `{}`", struct_name, field));
+
+            doc_2_delete.push(format!(
+                "- The second argument is the `{}` by which you delete [`{}`]",
+                field, struct_name
+            ));
+        }
+
+        // names, parameters, statements and where clauses of the functions of the indexes, `get_by_*`, `count_by_*`
+        // and `delete_by_*` filtering by all the fields of the index.
+        let mut fn_names_get_index: Vec<Ident> = Vec::new();
+        let mut fn_names_count_index: Vec<Ident> = Vec::new();
+        let mut params_for_index = Vec::new();
+        let mut loads_for_get_index = Vec::new();
+        let mut wheres_for_get_index = Vec::new();
+        let mut counts_for_index = Vec::new();
+        let mut wheres_for_count_index = Vec::new();
+
+        // indexes with a `#[ease(readonly)]` field do not get a delete function.
+        let mut fn_names_delete_index: Vec<Ident> = Vec::new();
+        let mut params_for_delete_index = Vec::new();
+        let mut statements_for_delete_index = Vec::new();
+        let mut wheres_for_delete_index = Vec::new();
+
+        let mut doc_title_get_index = Vec::new();
+        let mut doc_title_count_index = Vec::new();
+        let mut doc_2_get_index = Vec::new();
+        let mut doc_title_delete_index = Vec::new();
+        let mut doc_2_delete_index = Vec::new();
+
+        for index in &indexes {
+            let fn_name = index.iter().map(|&i| fields_fn_name[i].as_str()).collect::<Vec<_>>().join("_and_");
+            let params: Vec<Ident> = index.iter().map(|&i| format_ident!("query_{}", fields_name[i])).collect();
+            let param_types: Vec<&Type> = index.iter().map(|&i| &fields_type[i]).collect();
+            let filters: Vec<(&Ident, &Type, &Ident)> =
+                index.iter().zip(&params).map(|(&i, param)| (&fields_name[i], &fields_type[i], param)).collect();
+
+            let names = index.iter().map(|&i| format!("`{}`", fields_name[i])).collect::<Vec<_>>().join(" and ");
+            let params = quote! { #(#params: #param_ref #param_types),* };
+
+            let (load, bounds) = filter_by_all(&table_module, &filters, &param_ref, quote! { #table_module::table }, table_type.clone(), &|query, query_type| {
+                let (query, query_type) = connection.select_model(struct_name, query, query_type);
+
+                (
+                    connection.run(query, quote! { load::<#struct_name> }),
+                    connection.load_bound(query_type, quote! { Self }),
+                )
+            });
+
+            fn_names_get_index.push(connection.fn_name(format_ident!("get_by_{}", fn_name)));
+            loads_for_get_index.push(load);
+            wheres_for_get_index.push(connection.where_clause(&bounds));
+
+            let (count, bounds) = filter_by_all(&table_module, &filters, &param_ref, quote! { #table_module::table }, table_type.clone(), &|query, query_type| {
+                (
+                    connection.run(quote! { #query.count() }, quote! { get_result::<i64> }),
+                    connection.load_bound(quote! { diesel::dsl::Select<#query_type, diesel::dsl::count_star> }, quote! { i64 }),
+                )
+            });
+
+            fn_names_count_index.push(connection.fn_name(format_ident!("count_by_{}", fn_name)));
+            counts_for_index.push(count);
+            wheres_for_count_index.push(connection.where_clause(&bounds));
+
+            doc_title_get_index.push(format!("Get [`{}`] by filtering {}", struct_name, names));
+            doc_title_count_index.push(format!("Count the [`{}`] matching {}", struct_name, names));
+            doc_2_get_index.push(format!("- The next arguments are the {} by which you filter the [`{}`]", names, struct_name));
+
+            if index.iter().all(|&i| !fields_attrs[i].readonly) {
+                let (statement, bounds) = filter_by_all(&table_module, &filters, &param_ref, quote! { #table_module::table }, table_type.clone(), &|query, query_type| {
+                    (
+                        connection.run(quote! { diesel::delete(#query) }, quote! { execute }),
+                        connection.execute_bound(quote! {
+                            diesel::query_builder::DeleteStatement<
+                                #table_type,
+                                <#query_type as diesel::query_builder::IntoUpdateTarget>::WhereClause,
+                            >
+                        }),
+                    )
+                });
+
+                fn_names_delete_index.push(connection.fn_name(format_ident!("delete_by_{}", fn_name)));
+                params_for_delete_index.push(params.clone());
+                statements_for_delete_index.push(statement);
+                wheres_for_delete_index.push(connection.where_clause(&bounds));
+
+                doc_title_delete_index.push(format!("Delete [`{}`] by filtering {}", struct_name, names));
+                doc_2_delete_index.push(format!("- The next arguments are the {} by which you delete [`{}`]", names, struct_name));
+            }
+
+            params_for_index.push(params);
+        }
+
+        let insert_statement_type = quote! {
+            diesel::query_builder::InsertStatement<
+                #table_type,
+                <#param_types_for_insert as diesel::insertable::Insertable<#table_type>>::Values,
+            >
+        };
+
+        // without `RETURNING` the statement is only executed. The bound names the parts of `ExecuteDsl`, because the
+        // batch insert impls of SQLite overflow while `T` is not inferred yet.
+        let bound_for_insert = if returning {
+            connection.load_bound(insert_statement_type, quote! { Self })
+        } else {
+            let connection_type = connection.ty();
+
+            quote! {
+                #insert_statement_type:
+                    diesel::query_builder::QueryFragment<<#connection_type as diesel::Connection>::Backend>
+                    + diesel::query_builder::QueryId
+            }
+        };
+
+        // without `RETURNING` the inserted row is loaded again, by the rowid or the `AUTO_INCREMENT` primary key.
+        let statement_for_insert = if returning {
+            connection.run(
+                quote! { diesel::insert_into(#table_module::table).values(#params_for_insert) },
+                quote! { get_result::<#struct_name> },
+            )
+        } else {
+            let primary_key_names: Vec<&Ident> = primary_key.iter().map(|&i| &fields_name[i]).collect();
+
+            let condition = match connection.last_insert_condition(args.backend, &table_module, &primary_key_names) {
+                Some(condition) => condition,
+                None if args.ops.contains(Op::Insert) => {
+                    return Err(Error::new_spanned(
+                        &input.ident,
+                        "`insert` cannot load the inserted row without `RETURNING` and a primary key of one column, \
+                         mark the `AUTO_INCREMENT` column with `#[ease(primary_key)]` or use `exclude(insert)`",
+                    ))
+                }
+                None => quote! {},
+            };
+
+            let (query, _) = connection.select_model(
+                struct_name,
+                quote! { #table_module::table.filter(#condition) },
+                quote! {},
+            );
+
+            let execute = connection.run(
+                quote! { diesel::insert_into(#table_module::table).values(#params_for_insert) },
+                quote! { execute },
+            );
+            let first = connection.run(query, quote! { first::<#struct_name> });
+
+            version::transaction(quote! {
+                #execute?;
+
+                #first
+            })
+        };
+
+        // diesel 2 names the lifetime of the query in the bound of `T`.
+        let lifetimes_for_insert = if returning { connection.query_lifetimes() } else { vec![] };
+
+        let (generics_for_insert, where_for_insert) = match &args.insertable {
+            Some(insertable) => (
+                connection.generics_with(&lifetimes_of(insertable), &[]),
+                connection.where_clause(&[bound_for_insert]),
+            ),
+            None => (
+                connection.generics_with(&lifetimes_for_insert, &[format_ident!("T")]),
+                connection.where_clause_with(
+                    &[],
+                    &[quote! { T: diesel::insertable::Insertable<#table_type> }, bound_for_insert],
+                ),
+            ),
+        };
+
+        // query of all the rows, with its type.
+        let (query_for_get_all, query_type_for_get_all) =
+            connection.select_model(struct_name, quote! { #table_module::table }, table_type.clone());
+
+        let load_for_get_all = connection.run(query_for_get_all, quote! { load::<#struct_name> });
+        let where_for_get_all = connection.where_clause(&[connection.load_bound(query_type_for_get_all, quote! { Self })]);
+
+        let (load_for_get_all_paginated, bounds_for_get_all_paginated) =
+            paginate(quote! { #table_module::table }, table_type.clone());
+        let where_for_get_all_paginated = connection.where_clause(&[bounds_for_get_all_paginated]);
+
+        let (load_for_get_all_ordered, bounds_for_get_all_ordered) = order_by(quote! {}, Vec::new());
+        let where_for_get_all_ordered = connection.where_clause(&bounds_for_get_all_ordered);
+
+        let execute_for_delete_all = connection.run(quote! { diesel::delete(#table_module::table) }, quote! { execute });
+
+        let where_for_delete_all = connection.where_clause(&[connection.execute_bound(quote! {
+            diesel::query_builder::DeleteStatement<
+                #table_type,
+                <#table_type as diesel::query_builder::IntoUpdateTarget>::WhereClause,
+            >
+        })]);
+
+        // parameters and their types for find functions, one for each field of the primary key.
+        let params_for_find: Vec<Ident> = primary_key.iter().map(|&i| format_ident!("query_{}", fields_name[i])).collect();
+        let param_types_for_find: Vec<&Type> = primary_key.iter().map(|&i| &fields_type[i]).collect();
+
+        // the key given to `find`, a tuple for a composite primary key.
+        let (key_for_find, key_type_for_find) = match params_for_find.as_slice() {
+            [param] => {
+                let param_type = param_types_for_find[0];
+
+                (quote! { #param }, quote! { #param_ref #param_type })
+            }
+            params => (
+                quote! { (#(#params,)*) },
+                quote! { (#(#param_ref #param_types_for_find,)*) },
+            ),
+        };
+
+        // query of the row with the primary key, with its type.
+        let (query_for_find, query_type_for_find) = connection.select_model(
+            struct_name,
+            quote! { #table_module::table.find(#key_for_find) },
+            quote! { diesel::dsl::Find<#table_type, #key_type_for_find> },
+        );
+
+        let first_for_find = connection.run(query_for_find, quote! { first::<#struct_name> });
+        let where_for_find = connection.where_clause(&[connection.load_bound(
+            quote! { diesel::dsl::Limit<#query_type_for_find> },
+            quote! { Self },
+        )]);
+
+        let doc_2_find = match primary_key.as_slice() {
+            [i] => format!("- The second argument is the `{}` of the [`{}`]", fields_name[*i], struct_name),
+            _ => format!(
+                "- The next arguments are the fields of the primary key of the [`{}`]: {}",
+                struct_name,
+                primary_key.iter().map(|&i| format!("`{}`", fields_name[i])).collect::<Vec<_>>().join(", ")
+            ),
+        };
+
+        // the function updating by the primary key with the changeset.
+        let update_by_function = if has_changeset {
+            let fn_name_update_by = connection.fn_name(format_ident!(
+                "update_by_{}",
+                primary_key.iter().map(|&i| fields_fn_name[i].as_str()).collect::<Vec<_>>().join("_and_")
+            ));
+
+            let where_for_update_by = connection.where_clause(&[
+                connection.load_bound(
+                    quote! { diesel::dsl::Update<diesel::dsl::Find<#table_type, #key_type_for_find>, #changeset_name> },
+                    quote! { Self },
+                ),
+                connection.load_bound(quote! { diesel::dsl::Limit<#query_type_for_find> }, quote! { Self }),
+            ]);
+
+            // without `RETURNING` the row is loaded again by its primary key, which is not in the changeset.
+            let statement_for_update_by = if returning {
+                connection.run(
+                    quote! { diesel::update(#table_module::table.find(#key_for_find)).set(changeset) },
+                    quote! { get_result::<#struct_name> },
+                )
+            } else {
+                let execute = connection.run(
+                    quote! { diesel::update(#table_module::table.find(#key_for_find)).set(changeset) },
+                    quote! { execute },
+                );
+                version::transaction(quote! {
+                    #execute?;
+
+                    #first_for_find
+                })
+            };
+
+            let doc_title_update_by = format!("Update the [`{}`] with the given primary key by a [`{}`]", struct_name, changeset_name);
+            let doc_3_update_by = format!("- The last argument is the [`{}`] with the new values", changeset_name);
+
+            quote! {
+                impl #struct_name {
+                    #[doc = #doc_title_update_by]
+                    #[doc = ""]
+                    #[doc = "# Arguments"]
+                    #[doc = ""]
+                    #[doc = #doc_2_find]
+                    #[doc = ""]
+                    #[doc = #doc_3_update_by]
+                    #[doc = ""]
+                    #[doc = "All the changes are made by one statement. An empty changeset changes nothing, the row is only loaded. Returns `Err(diesel::result::Error::NotFound)` if there is no such row."]
+                    pub #asyncness fn #fn_name_update_by #connection_generics (connection: &mut #connection_type, #(#params_for_find: #param_ref #param_types_for_find,)* changeset: #changeset_name) -> diesel::result::QueryResult<#struct_name> #where_for_update_by {
+                        #prelude
+
+                        // diesel cannot build an `UPDATE` without columns, it would be an `EmptyChangeset` error.
+                        if changeset.is_empty() {
+                            return #first_for_find;
+                        }
+
+                        #statement_for_update_by
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let get_functions = if args.ops.contains(Op::Get) {
+            quote! {
+                #(           
+                    #[doc = #doc_title_get]
+                    #[doc = ""]                     
+                    #[doc = "# Arguments"]
+                    #[doc = ""]
+                    #[doc = #doc_2_get]
+                    #[doc = ""]                     
+                    // get functions                           
+                    pub #asyncness fn #fn_names_get #connection_generics (connection: &mut #connection_type, #params_for_get: #param_ref #param_types_for_get) ->
+                    diesel::result::QueryResult<Vec<#fn_return_types>> #wheres_for_get {
+                        use #table_module::dsl::*;
+                        #prelude
+                        let results = #loads_for_get;
+
+                        results
+                    }
+                )*
+
+                #(
+                    #[doc = #doc_title_get_single]
+                    #[doc = ""]
+                    #[doc = "# Arguments"]
+                    #[doc = ""]
+                    #[doc = #doc_2_get_single]
+                    #[doc = ""]
+                    // get functions by a unique field
+                    pub #asyncness fn #fn_names_get_single #connection_generics (connection: &mut #connection_type, #params_for_get_single: #param_ref #param_types_for_get_single) ->
+                    diesel::result::QueryResult<Option<#fn_return_types_single>> #wheres_for_get_single {
+                        use #table_module::dsl::*;
+                        #prelude
+                        #loads_for_get_single
+                    }
+                )*
+            }
+        } else {
+            quote! {}
+        };
+
+        let get2_functions = if args.ops.contains(Op::GetBy) {
+            quote! {
+                #(
+                    #[doc = #doc_title_get2]
+                    #[doc = ""]
+                    #[doc = "# Arguments"]
+                    #[doc = ""]
+                    #[doc = #doc_2_get2]
+                    #[doc = ""]
+                    // get2 functions                
+                    pub #asyncness fn #fn_names_get2 #connection_generics (connection: &mut #connection_type, #params_for_get2: #param_ref #param_types_for_get2) -> diesel::result::QueryResult<Vec<#struct_name>> #wheres_for_get2 {
+                        use #table_module::dsl::*;
+                        #prelude
+                        let results = #loads_for_get2;
+
+                        results
+                    }
+                )*
+
+                #(
+                    #[doc = #doc_title_get_index]
+                    #[doc = ""]
+                    #[doc = "# Arguments"]
+                    #[doc = ""]
+                    #[doc = #doc_2_get_index]
+                    #[doc = ""]
+                    pub #asyncness fn #fn_names_get_index #connection_generics (connection: &mut #connection_type, #params_for_index) -> diesel::result::QueryResult<Vec<#struct_name>> #wheres_for_get_index {
+                        #prelude
+                        #loads_for_get_index
+                    }
+
+                    #[doc = #doc_title_count_index]
+                    #[doc = ""]
+                    #[doc = "# Arguments"]
+                    #[doc = ""]
+                    #[doc = #doc_2_get_index]
+                    #[doc = ""]
+                    pub #asyncness fn #fn_names_count_index #connection_generics (connection: &mut #connection_type, #params_for_index) -> diesel::result::QueryResult<i64> #wheres_for_count_index {
+                        #prelude
+                        #counts_for_index
+                    }
+                )*
+
+                #(
+                    #[doc = #doc_title_get2_paginated]
+                    #[doc = ""]
+                    #[doc = "# Arguments"]
+                    #[doc = ""]
+                    #[doc = #doc_2_get2]
+                    #[doc = #doc_page]
+                    #[doc = #doc_per_page]
+                    #[doc = ""]
+                    pub #asyncness fn #fn_names_get2_paginated #connection_generics (connection: &mut #connection_type, #params_for_get2: #param_ref #param_types_for_get2, page: i64, per_page: i64) -> diesel::result::QueryResult<#page_name> #wheres_for_get2_paginated {
+                        #prelude
+                        #loads_for_get2_paginated
+                    }
+                )*
+
+                #(
+                    #[doc = #doc_title_get2_ordered]
+                    #[doc = ""]
+                    #[doc = "# Arguments"]
+                    #[doc = ""]
+                    #[doc = #doc_2_get2]
+                    #[doc = #doc_column]
+                    #[doc = #doc_direction]
+                    #[doc = ""]
+                    pub fn #fn_names_get2_ordered #connection_generics (connection: #boxed_connection_param, #params_for_get2: #param_ref #param_types_for_get2, column: #column_name, direction: #direction_name) -> #boxed_return_type #wheres_for_get2_ordered {
+                        #prelude
+                        #loads_for_get2_ordered
+                    }
+                )*
+
+                #(
+                    #[doc = #doc_title_get_one]
+                    #[doc = ""]
+                    #[doc = "# Arguments"]
+                    #[doc = ""]
+                    #[doc = #doc_2_get_one]
+                    #[doc = ""]
+                    // get_one functions
+                    pub #asyncness fn #fn_names_get_one #connection_generics (connection: &mut #connection_type, #params_for_get_one: #param_ref #param_types_for_get_one) -> diesel::result::QueryResult<Option<#struct_name>> #wheres_for_get_one {
+                        use #table_module::dsl::*;
+                        #prelude
+                        #loads_for_get_one
+                    }
+                )*
+            }
+        } else {
+            quote! {}
+        };
+
+        let get_block = if args.ops.contains(Op::Get) || args.ops.contains(Op::GetBy) {
+            quote! {
+                /// Functions for getting data from database
+                /// 
+                /// # Example
+                /// 
+                /// If you have a struct like this:
+                /// 
+                /// ```rust,ignore
+                /// #[diesel_ease(PgConnection)]
+                /// #[derive(Queryable, Clone, Debug, PartialEq)]
+                /// struct User {
+                ///    id: i32,
+                ///    name: String,
+                /// }
+                /// ```
+                /// 
+                /// Then you will get functions for getting `name` by `id` and `id` by `name`.
+                /// 
+                /// ```rust,ignore
+                /// const USER_ID: i32 = 19;
+                /// 
+                /// let connection = establish_connection();
+                /// 
+                /// // get the name of the User
+                /// let name = User::get_names_by_id(&connection, &USER_ID).unwrap();
+                /// 
+                /// // get the id of the User
+                /// let id = User::get_ids_by_name(&connection, &name[0]).unwrap();
+                /// 
+                /// assert_eq!(id[0], USER_ID);
+                /// 
+                /// // You can also get the User by id or name
+                /// let user: Vec<User> = User::get_by_id(&connection, &USER_ID).unwrap();
+                /// ```
+                /// 
+                impl #struct_name {
+                    #get_functions
+
+                    #get2_functions
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let update_block = if args.ops.contains(Op::Update) {
+            quote! {
+                /// Functions for updating data from database
+                /// 
+                /// # Example
+                /// 
+                /// If you have a struct like this:
+                /// 
+                /// ```rust,ignore
+                /// #[diesel_ease(PgConnection)]
+                /// #[derive(Queryable, Clone, Debug, PartialEq)]
+                /// struct User {
+                ///    id: i32,
+                ///    name: String,
+                /// }
+                /// ```
+                /// 
+                /// Then you will get functions for updating `name` by `id` and `id` by `name`.
+                /// 
+                /// ```rust,ignore
+                /// let connection = establish_connection();
+                /// 
+                /// // get the old user
+                /// let old_user: Vec<User> = User::get_by_name(&connection, &"John".into()).unwrap();
+                /// 
+                /// // update the user, all the updated rows are returned
+                /// let new_updated_users: Vec<User> =
+                ///     User::update_names_by_id(&connection, &(old_user[0].id), &String::from("Johny Depth"))
+                ///         .unwrap();
+                /// 
+                /// println!("OLD user: {:?}", old_user[0]);
+                /// println!("NEW user: {:?}", new_updated_users[0]);
+                /// 
+                /// assert_ne!(old_user[0].name, new_updated_users[0].name);
+                /// 
+                /// // or only get the number of updated rows
+                /// let updated: usize = User::update_names_by_name_count(&connection, &"John".into(), &"Johny".into()).unwrap();
+                /// ```
+                // Update functions
+                impl #struct_name {
+                    #(
+                        #[doc = #doc_title_update]
+                        #[doc = ""]
+                        #[doc = "# Arguments"]
+                        #[doc = ""]
+                        #[doc = #doc_2_update]
+                        #[doc = ""]
+                        #[doc = #doc_3_update]
+                        #[doc = ""]
+                        #[doc = "Returns all the updated rows, an empty `Vec` if no row matches."]
+                        // update functions
+                        pub #asyncness fn #fn_names_update #connection_generics (connection: &mut #connection_type, #params_for_update: #param_ref #param_types_for_update, #new_fields_params: #param_ref #new_fields_types) -> diesel::result::QueryResult<Vec<#struct_name>> #wheres_for_update {
+                            use #table_module::dsl::*;
+                            #prelude
+                            #statements_for_update
+                        }
+                    )*
+
+                    #(
+                        #[doc = #doc_title_update]
+                        #[doc = ""]
+                        #[doc = "# Arguments"]
+                        #[doc = ""]
+                        #[doc = #doc_2_update]
+                        #[doc = ""]
+                        #[doc = #doc_3_update]
+                        #[doc = ""]
+                        #[doc = "Returns the number of updated rows."]
+                        // update functions returning the number of updated rows
+                        pub #asyncness fn #fn_names_update_count #connection_generics (connection: &mut #connection_type, #params_for_update: #param_ref #param_types_for_update, #new_fields_params: #param_ref #new_fields_types) -> diesel::result::QueryResult<usize> #wheres_for_update_count {
+                            use #table_module::dsl::*;
+                            #prelude
+                            #statements_for_update_count
+                        }
+                    )*
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let insert_block = if args.ops.contains(Op::Insert) {
+            quote! {
+                /// Functions for inserting data into database
+                /// 
+                /// # Example
+                /// 
+                /// If you have structs like this:
+                /// 
+                /// ```rust,ignore
+                /// #[diesel_ease(PgConnection)]
+                /// #[derive(Queryable, Clone, Debug, PartialEq)]
+                /// struct User {
+                ///   id: i32,
+                ///   name: String,
+                /// }
+                /// 
+                /// #[derive(Insertable)]
+                /// #[table_name = "users"]
+                /// struct NewUser {
+                ///   name: String,
+                /// }
+                ///  
+                /// ```            
+                /// Then you use the `insert` method to insert a new user.
+                /// 
+                /// ```rust,ignore
+                /// let connection = establish_connection();
+                /// 
+                /// let new_user = NewUser {
+                ///     name: "Sean".into(),
+                /// };
+                /// 
+                /// let inserted_user = User::insert(&connection, new_user).unwrap();
+                /// 
+                /// println!("New User: {:?}", inserted_user);            
+                /// ```
+                impl #struct_name {
+
+                    #[doc = #doc_title_insert]
+                    #[doc = ""]
+                    #[doc = "# Arguments"]
+                    #[doc = ""]
+                    #[doc = #doc_2_insert]
+                    #[doc = ""]
+                    #[doc = #doc_3_insert]
+                    // insert function
+                    pub #asyncness fn #fn_name_insert #generics_for_insert (connection: &mut #connection_type, #params_for_insert: #param_types_for_insert) -> diesel::result::QueryResult<#struct_name> #where_for_insert {
+                        #prelude
+                        #statement_for_insert
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let delete_block = if args.ops.contains(Op::DeleteBy) {
+            quote! {
+                /// Functions for deleting data from database
+                /// 
+                /// # Example
+                /// 
+                /// If you have structs like this:
+                /// 
+                /// ```rust,ignore
+                /// #[diesel_ease(PgConnection)]
+                /// #[derive(Queryable, Clone, Debug, PartialEq)]
+                /// struct User {
+                ///  id: i32,
+                ///  name: String,
+                /// }
+                /// ```
+                /// 
+                /// Then you can delete `User` by `id` or `name`.
+                /// 
+                /// ```rust,ignore
+                /// let connection = establish_connection();
+                /// 
+                /// // delete by id
+                /// let deleted_user: usize = User::delete_by_id(&connection, &6).unwrap();
+                /// 
+                /// println!("Deleted {} users by id", deleted_user);
+                /// 
+                /// // delete by name
+                /// let deleted_user = User::delete_by_name(&connection, &String::from("Python Lover")).unwrap();
+                /// 
+                /// println!("Deleted {} users by name", deleted_user);
+                /// ```
+                ///         
+                impl #struct_name {
+                    #(
+                        #[doc = #doc_title_delete]
+                        #[doc = ""]
+                        #[doc = "# Arguments"]
+                        #[doc = ""]
+                        #[doc = #doc_2_delete]
+                        #[doc = ""]
+                        // delete functions
+                        pub #asyncness fn #fn_names_delete #connection_generics (connection: &mut #connection_type, #params_for_delete: #param_ref #param_types_for_delete) -> diesel::result::QueryResult<usize> #wheres_for_delete {
+                            use #table_module::dsl::*;
+                            #prelude
+                            let num_deleted = #statements_for_delete;
+
+                            num_deleted
+                        }
+                    )*
+
+                    #(
+                        #[doc = #doc_title_delete_index]
+                        #[doc = ""]
+                        #[doc = "# Arguments"]
+                        #[doc = ""]
+                        #[doc = #doc_2_delete_index]
+                        #[doc = ""]
+                        pub #asyncness fn #fn_names_delete_index #connection_generics (connection: &mut #connection_type, #params_for_delete_index) -> diesel::result::QueryResult<usize> #wheres_for_delete_index {
+                            #prelude
+                            #statements_for_delete_index
+                        }
+                    )*
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let find_block = if args.ops.contains(Op::Find) && !primary_key.is_empty() {
+            quote! {
+                /// Functions for getting a row by its primary key
+                /// 
+                /// # Example
+                /// 
+                /// If you have a struct like this:
+                /// 
+                /// ```rust,ignore
+                /// #[diesel_ease(PgConnection)]
+                /// #[derive(Queryable, Clone, Debug, PartialEq)]
+                /// struct User {
+                ///    id: i32,
+                ///    name: String,
+                /// }
+                /// ```
+                /// 
+                /// Then you can find the `User` by `id`, without indexing into a `Vec`.
+                /// 
+                /// ```rust,ignore
+                /// let mut connection = establish_connection();
+                /// 
+                /// // `None` if there is no user with the id
+                /// let user: Option<User> = User::find(&mut connection, &19).unwrap();
+                /// 
+                /// // `Err(diesel::result::Error::NotFound)` if there is no user with the id
+                /// let user: User = User::find_or_err(&mut connection, &19).unwrap();
+                /// ```
+                impl #struct_name {
+                    #[doc = "Find the row with the given primary key, or `None` if there is no such row"]
+                    #[doc = ""]
+                    #[doc = "# Arguments"]
+                    #[doc = ""]
+                    #[doc = #doc_2_find]
+                    #[doc = ""]
+                    pub #asyncness fn #fn_name_find #connection_generics (connection: &mut #connection_type, #(#params_for_find: #param_ref #param_types_for_find),*) -> diesel::result::QueryResult<Option<#struct_name>> #where_for_find {
+                        #prelude
+                        #first_for_find.optional()
+                    }
+
+                    #[doc = "Find the row with the given primary key, or `Err(diesel::result::Error::NotFound)` if there is no such row"]
+                    #[doc = ""]
+                    #[doc = "# Arguments"]
+                    #[doc = ""]
+                    #[doc = #doc_2_find]
+                    #[doc = ""]
+                    pub #asyncness fn #fn_name_find_or_err #connection_generics (connection: &mut #connection_type, #(#params_for_find: #param_ref #param_types_for_find),*) -> diesel::result::QueryResult<#struct_name> #where_for_find {
+                        #prelude
+                        #first_for_find
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let get_all_ordered_function = if has_order {
+            quote! {
+                /// Get all data from database, ordered by a column
+                ///
+                /// # Arguments
+                ///
+                #[doc = #doc_column]
+                #[doc = #doc_direction]
+                pub fn #fn_name_get_all_ordered #connection_generics (connection: #boxed_connection_param, column: #column_name, direction: #direction_name) -> #boxed_return_type #where_for_get_all_ordered {
+                    #prelude
+                    #load_for_get_all_ordered
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let get_all_function = if args.ops.contains(Op::GetAll) {
+            quote! {
+                /// Get all data from database
+                pub #asyncness fn #fn_name_get_all #connection_generics (connection: &mut #connection_type) -> diesel::result::QueryResult<Vec<#struct_name>> #where_for_get_all {
+                    #prelude
+                    #load_for_get_all
+                }
+
+                /// Get a page of the data from database
+                ///
+                /// # Arguments
+                ///
+                #[doc = #doc_page]
+                #[doc = #doc_per_page]
+                pub #asyncness fn #fn_name_get_all_paginated #connection_generics (connection: &mut #connection_type, page: i64, per_page: i64) -> diesel::result::QueryResult<#page_name> #where_for_get_all_paginated {
+                    #prelude
+                    #load_for_get_all_paginated
+                }
+
+                #get_all_ordered_function
+            }
+        } else {
+            quote! {}
+        };
+
+        // `get_after_{field}` and `get_before_{field}` for the cursor fields, in ascending and descending order.
+        let mut cursor_functions = Vec::new();
+
+        if args.ops.contains(Op::GetAll) {
+            for ((&i, keys), cursor_name) in cursor_fields.iter().zip(&cursor_keys).zip(&cursor_names) {
+                let doc_keys: Vec<String> = keys.iter().map(|&j| format!("`{}`", fields_fn_name[j])).collect();
+                let doc_keys = doc_keys.join(" then ");
+
+                let directions = [
+                    ("after", "ascending", quote! { gt }, quote! { diesel::dsl::Gt }, quote! { asc }, quote! { diesel::dsl::Asc }),
+                    ("before", "descending", quote! { lt }, quote! { diesel::dsl::Lt }, quote! { desc }, quote! { diesel::dsl::Desc }),
+                ];
+
+                for (direction, order_name, comparison, comparison_type, order, order_type) in directions {
+                    let fn_name = connection.fn_name(format_ident!("get_{}_{}", direction, fields_fn_name[i]));
+
+                    // `a > x OR (a = x AND (b > y OR (b = y AND ...)))`, built from the last key.
+                    let mut predicate = None;
+
+                    for &j in keys.iter().rev() {
+                        let key = &fields_name[j];
+                        let key_type = &fields_type[j];
+                        let column = quote! { #table_module::#key };
+                        // the async functions own the cursor, each use of a value gets its own copy.
+                        let value = if matches!(connection, Connection::Async) {
+                            quote! { cursor.#key.clone() }
+                        } else {
+                            quote! { &cursor.#key }
+                        };
+                        let value_type = quote! { #param_ref #key_type };
+
+                        predicate = Some(match predicate {
+                            None => (quote! { #column.#comparison(#value) }, quote! { #comparison_type<#column, #value_type> }),
+                            Some((rest, rest_type)) => (
+                                quote! { #column.#comparison(#value).or(#column.eq(#value).and(#rest)) },
+                                quote! {
+                                    diesel::dsl::Or<
+                                        #comparison_type<#column, #value_type>,
+                                        diesel::dsl::And<diesel::dsl::Eq<#column, #value_type>, #rest_type>,
+                                    >
+                                },
+                            ),
+                        });
+                    }
+
+                    let (predicate, predicate_type) = predicate.expect("a cursor has at least one key");
+                    let key_names: Vec<&Ident> = keys.iter().map(|&j| &fields_name[j]).collect();
+
+                    let key_columns: Vec<proc_macro2::TokenStream> = keys
+                        .iter()
+                        .map(|&j| {
+                            let key = &fields_name[j];
+
+                            quote! { #table_module::#key }
+                        })
+                        .collect();
+                    let (ordering, ordering_type) = match key_columns.as_slice() {
+                        [column] => (quote! { #column.#order() }, quote! { #order_type<#column> }),
+                        columns => (quote! { (#(#columns.#order(),)*) }, quote! { (#(#order_type<#columns>,)*) }),
+                    };
+
+                    let (query_from_cursor, query_type_from_cursor) = connection.select_model(
+                        struct_name,
+                        quote! { #table_module::table.filter(#predicate).order_by(#ordering) },
+                        quote! { diesel::dsl::Order<diesel::dsl::Filter<#table_type, #predicate_type>, #ordering_type> },
+                    );
+
+                    let (query_from_start, query_type_from_start) = connection.select_model(
+                        struct_name,
+                        quote! { #table_module::table.order_by(#ordering) },
+                        quote! { diesel::dsl::Order<#table_type, #ordering_type> },
+                    );
+
+                    let load_from_cursor = connection.run(quote! { #query_from_cursor.limit(limit) }, quote! { load::<#struct_name> });
+                    let load_from_start = connection.run(quote! { #query_from_start.limit(limit) }, quote! { load::<#struct_name> });
+
+                    let where_clause = connection.where_clause(&[
+                        connection.load_bound(quote! { diesel::dsl::Limit<#query_type_from_cursor> }, quote! { Self }),
+                        connection.load_bound(quote! { diesel::dsl::Limit<#query_type_from_start> }, quote! { Self }),
+                    ]);
+
+                    let doc_title = format!(
+                        "Get the [`{}`] {} the cursor, in {} order of {}",
+                        struct_name, direction, order_name, doc_keys
+                    );
+                    let doc_2 = format!(
+                        "- `cursor` is the `next` of the previous call, or `None` for the {} rows",
+                        if direction == "after" { "first" } else { "last" }
+                    );
+
+                    cursor_functions.push(quote! {
+                        #[doc = #doc_title]
+                        #[doc = ""]
+                        #[doc = "# Arguments"]
+                        #[doc = ""]
+                        #[doc = #doc_2]
+                        #[doc = "- `limit` is the maximum number of rows, at least 1"]
+                        #[doc = ""]
+                        pub #asyncness fn #fn_name #connection_generics (connection: &mut #connection_type, cursor: Option<#param_ref #cursor_name>, limit: i64) -> diesel::result::QueryResult<#cursor_page_name<#cursor_name>> #where_clause {
+                            #prelude
+
+                            let limit = limit.max(1);
+
+                            let items = match cursor {
+                                Some(cursor) => #load_from_cursor?,
+                                None => #load_from_start?,
+                            };
+
+                            // with fewer rows than `limit` there are no more rows.
+                            let next = if items.len() as i64 == limit {
+                                items.last().map(|item| #cursor_name { #(#key_names: item.#key_names.clone(),)* })
+                            } else {
+                                None
+                            };
+
+                            Ok(#cursor_page_name { items, next })
+                        }
+                    });
+                }
+            }
+        }
+
+        // `find_where` boxes the query, so that a filter is only added for the fields of the filter which are set.
+        let find_where_function = if args.ops.contains(Op::Query) && !filter_fields.is_empty() {
+            let by_value = matches!(connection, Connection::Async);
+
+            let mut filters = Vec::new();
+            let mut bounds = boxed_bounds.clone();
+
+            let mut filter_bound = |predicate_type: proc_macro2::TokenStream| {
+                bounds.push(quote! {
+                    #boxed_type: diesel::query_dsl::methods::FilterDsl<#predicate_type, Output = #boxed_type>
+                });
+            };
+
+            for (i, name, comparison) in &filter_fields {
+                let field = &fields_name[*i];
+                let column = quote! { #table_module::#field };
+                let ty = &fields_type[*i];
+                let value = if by_value { quote! { filter.#name } } else { quote! { &filter.#name } };
+
+                match (*comparison, option_inner(ty)) {
+                    ("eq", Some(inner)) => {
+                        filters.push(quote! {
+                            match #value {
+                                Some(Some(value)) => query = query.filter(#column.eq(value)),
+                                Some(None) => query = query.filter(#column.is_null()),
+                                None => {}
+                            }
+                        });
+
+                        filter_bound(quote! { diesel::dsl::Eq<#column, #param_ref #inner> });
+                        filter_bound(quote! { diesel::dsl::IsNull<#column> });
+                    }
+                    (comparison, inner) => {
+                        let method = format_ident!("{}", comparison);
+                        let method_type = format_ident!("{}", naming::to_camel_case(comparison));
+                        let inner = inner.unwrap_or(ty);
+
+                        filters.push(quote! {
+                            if let Some(value) = #value {
+                                query = query.filter(#column.#method(value));
+                            }
+                        });
+
+                        filter_bound(quote! { diesel::dsl::#method_type<#column, #param_ref #inner> });
+                    }
+                }
+            }
+
+            let load = load_boxed(&mut bounds);
+
+            let where_for_find_where = connection.where_clause(&bounds);
+
+            quote! {
+                /// Get the rows matching the filter, only the fields of the filter which are `Some` are compared
+                ///
+                /// # Arguments
+                ///
+                /// - The second argument is the filter
+                pub fn #fn_name_find_where #connection_generics (connection: #boxed_connection_param, filter: #param_ref #filter_name) -> #boxed_return_type #where_for_find_where {
+                    #prelude
+
+                    let mut query: #boxed_type = #table_module::table.into_boxed();
+
+                    #(#filters)*
+
+                    #load
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let delete_all_function = if args.ops.contains(Op::DeleteAll) {
+            quote! {
+                /// Delete all data from database
+                pub #asyncness fn #fn_name_delete_all #connection_generics (connection: &mut #connection_type) -> diesel::result::QueryResult<usize> #where_for_delete_all {
+                    #prelude
+                    #execute_for_delete_all
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        Ok(quote! {
+            #get_block
+
+            #update_block
+
+            #update_by_function
+
+            #insert_block
+
+            #delete_block
+
+            #find_block
+
+            impl #struct_name {
+                #get_all_function
+
+                #(#cursor_functions)*
+
+                #find_where_function
+
+                #delete_all_function
+            }
+        })
+    };
+
+    let sync_functions = functions(&connection)?;
+
+    // a wrong `backend = ...` would only fail when a row is loaded again, it is checked against the connection type.
+    let check_backend = connection.check_backend(args.backend);
+
+    check_function_names(struct_name, &sync_functions)?;
+
+    // with `pool = ...` the functions are also generated taking a pool, with `_pooled` appended.
+    let pooled_functions = match (&args.pool, &connection) {
+        (Some(pool), Connection::Type(connection_type)) => {
+            pool::pooled_functions(pool, connection_type, sync_functions.clone())?
+        }
+        (Some(_), _) => {
+            return Err(Error::new(
+                proc_macro2::Span::call_site(),
+                "`pool` needs a connection type, e.g. `#[diesel_ease(PgConnection, pool = r2d2)]`",
+            ))
+        }
+        (None, _) => quote! {},
+    };
+
+    // with the `async` feature the functions are also generated for async connections, with `_async` appended.
+    let async_functions = if cfg!(feature = "async") {
+        functions(&Connection::Async)?
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        #item
+
+        #generated_insertable
+
+        #generated_changeset
+
+        #generated_page
+
+        #generated_cursor_page
+
+        #generated_order
+
+        #generated_builders
+
+        #generated_filter
+
+        #check_backend
+
+        #sync_functions
+
+        #pooled_functions
+
+        #async_functions
+    })
+}
+
+/// Check that the generated `functions` have different names.
+///
+/// The names come from the fields, their `#[ease(rename = "...")]` and `naming = ...`, so two functions can get the
+/// same name, e.g. `get_title_by_email` returning a `Vec` and returning one value for a unique `email` with
+/// `naming = none`.
+fn check_function_names(struct_name: &Ident, functions: &proc_macro2::TokenStream) -> Result<()> {
+    let file: File = parse2(functions.clone())?;
+    let mut names: Vec<Ident> = Vec::new();
+
+    let methods = file.items.iter().flat_map(|item| match item {
+        Item::Impl(item_impl) => item_impl.items.iter().collect(),
+        _ => vec![],
+    });
+
+    for impl_item in methods {
+        let name = match impl_item {
+            ImplItem::Method(method) => &method.sig.ident,
+            _ => continue,
+        };
+
+        if names.contains(name) {
+            return Err(Error::new_spanned(
+                struct_name,
+                format!(
+                    "two functions of `{}` are named `{}`, use another `naming = ...` or `#[ease(rename = \"...\")]`, \
+                     see the naming notes in the docs",
+                    struct_name, name
+                ),
+            ));
+        }
+
+        names.push(name.clone());
+    }
+
+    Ok(())
+}
+
+/// Named lifetimes in the type, e.g. `'a` in `NewPost<'a>`.
+fn lifetimes_of(ty: &Type) -> Vec<Lifetime> {
+    let mut lifetimes: Vec<Lifetime> = Vec::new();
+
+    let mut push = |lifetime: &Lifetime| {
+        if lifetime.ident != "static" && lifetime.ident != "_" && !lifetimes.contains(lifetime) {
+            lifetimes.push(lifetime.clone());
+        }
+    };
+
+    match ty {
+        Type::Path(path) => {
+            for segment in &path.path.segments {
+                if let PathArguments::AngleBracketed(arguments) = &segment.arguments {
+                    for argument in &arguments.args {
+                        match argument {
+                            GenericArgument::Lifetime(lifetime) => push(lifetime),
+                            GenericArgument::Type(ty) => lifetimes_of(ty).iter().for_each(&mut push),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+        Type::Reference(reference) => {
+            if let Some(lifetime) = &reference.lifetime {
+                push(lifetime);
+            }
+
+            lifetimes_of(&reference.elem).iter().for_each(&mut push);
+        }
+        _ => {}
+    }
+
+    lifetimes
+}
+
+/// The `T` of an `Option<T>` type, i.e. if the column is nullable.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    if let Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            if let PathArguments::AngleBracketed(arguments) = &segment.arguments {
+                if segment.ident == "Option" {
+                    if let Some(GenericArgument::Type(inner)) = arguments.args.first() {
+                        return Some(inner);
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Whether values of the type can be compared with `>` and `<`, i.e. numbers and times.
+fn is_ordered(ty: &Type) -> bool {
+    const ORDERED: &[&str] = &[
+        "i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "f32", "f64", "BigDecimal", "NaiveDate", "NaiveTime",
+        "NaiveDateTime", "DateTime", "Date", "Time", "PrimitiveDateTime", "OffsetDateTime", "SystemTime",
+    ];
+
+    match ty {
+        Type::Path(path) => path.path.segments.last().is_some_and(|segment| ORDERED.iter().any(|name| segment.ident == name)),
+        _ => false,
+    }
+}
+
+/// Whether the type is text, which can be matched with `LIKE`.
+fn is_text(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.last().is_some_and(|segment| segment.ident == "String"),
+        _ => false,
+    }
+}
+
+/// Build a statement that filters the table by `field`.
+///
+/// `statement` gets the predicate to put into `.filter()` and the type of it, and returns the statement and the
+/// bound it needs with a generic connection. For nullable fields a `match` is generated so that `None` matches
+/// `IS NULL` and `Some(value)` matches `= value`.
+fn filter_by(
+    table_module: &Path,
+    field: &Ident,
+    ty: &Type,
+    param: &Ident,
+    param_ref: &proc_macro2::TokenStream,
+    statement: impl Fn(proc_macro2::TokenStream, proc_macro2::TokenStream) -> (proc_macro2::TokenStream, proc_macro2::TokenStream),
+) -> (proc_macro2::TokenStream, Vec<proc_macro2::TokenStream>) {
+    let column = quote! { #table_module::#field };
+
+    match option_inner(ty) {
+        Some(inner) => {
+            let (some, some_bound) = statement(
+                quote! { #column.eq(#param) },
+                quote! { diesel::dsl::Eq<#column, #param_ref #inner> },
+            );
+            let (none, none_bound) = statement(quote! { #column.is_null() }, quote! { diesel::dsl::IsNull<#column> });
+
+            let statement = quote! {
+                match #param {
+                    Some(#param) => #some,
+                    None => #none,
+                }
+            };
+
+            (statement, vec![some_bound, none_bound])
+        }
+        None => {
+            let (statement, bound) = statement(
+                quote! { #column.eq(#param) },
+                quote! { diesel::dsl::Eq<#column, #param_ref #ty> },
+            );
+
+            (statement, vec![bound])
+        }
+    }
+}
+
+/// Filter the `query` by several fields, which all have to match. Returns the statement and the bounds it needs.
+///
+/// `fields` are the fields with their types and the parameters they are compared to. `statement` gets the filtered
+/// query and its type, it is called once for each combination of the nullable fields being `NULL` or not.
+fn filter_by_all(
+    table_module: &Path,
+    fields: &[(&Ident, &Type, &Ident)],
+    param_ref: &proc_macro2::TokenStream,
+    query: proc_macro2::TokenStream,
+    query_type: proc_macro2::TokenStream,
+    statement: &dyn Fn(proc_macro2::TokenStream, proc_macro2::TokenStream) -> (proc_macro2::TokenStream, proc_macro2::TokenStream),
+) -> (proc_macro2::TokenStream, Vec<proc_macro2::TokenStream>) {
+    let (&(field, ty, param), rest) = match fields.split_first() {
+        Some(first) => first,
+        None => {
+            let (statement, bound) = statement(query, query_type);
+
+            return (statement, vec![bound]);
+        }
+    };
+
+    filter_by(table_module, field, ty, param, param_ref, |predicate, predicate_type| {
+        let (statement, bounds) = filter_by_all(
+            table_module,
+            rest,
+            param_ref,
+            quote! { #query.filter(#predicate) },
+            quote! { diesel::dsl::Filter<#query_type, #predicate_type> },
+            statement,
+        );
+
+        (statement, quote! { #(#bounds),* })
+    })
+}
+
+/// Get the table from a `#[table_name = "..."]` or `#[diesel(table_name = ...)]` attribute on the struct.
+fn table_from_attrs(attrs: &[Attribute]) -> Result<Option<Path>> {
+    for attr in attrs {
+        if attr.path.is_ident("table_name") {
+            if let Meta::NameValue(MetaNameValue { lit: Lit::Str(table), .. }) = attr.parse_meta()? {
+                return table.parse::<Ident>().map(|table| Some(table.into()));
+            }
+        }
+    }
+
+    for (key, value) in version::diesel_options(attrs)? {
+        if key == "table_name" {
+            let table_value = |input: parse::ParseStream| {
+                input.parse::<Token![=]>()?;
+                input.parse::<Path>()
+            };
+
+            let table = parse::Parser::parse2(table_value, value)?;
+
+            return Ok(Some(table));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Get the named fields of the struct, checking that the macro supports them.
+fn named_fields(input: &ItemStruct) -> Result<&punctuated::Punctuated<Field, Token![,]>> {
+    let fields = match &input.fields {
+        Fields::Named(named_fields) => &named_fields.named,
+        Fields::Unnamed(unnamed_fields) => {
+            return Err(Error::new_spanned(
+                unnamed_fields,
+                "tuple structs are not supported, use a struct with named fields",
+            ))
+        }
+        Fields::Unit => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "unit structs are not supported, use a struct with named fields",
+            ))
+        }
+    };
+
+    // the errors on the fields come first, e.g. `&'a str` is what makes a struct need `<'a>`.
+    let generics_error = if input.generics.params.is_empty() {
+        None
+    } else {
+        Some(Error::new_spanned(
+            &input.generics,
+            "generic structs are not supported, use owned field types without lifetimes or type parameters",
+        ))
+    };
+
+    let mut errors = fields.iter().filter_map(|field| check_type(&field.ty).err()).chain(generics_error);
+
+    if let Some(mut error) = errors.next() {
+        errors.for_each(|other| error.combine(other));
+
+        return Err(error);
+    }
+
+    Ok(fields)
+}
+
+/// Check that the field type is one the generated functions can take and return.
+fn check_type(ty: &Type) -> Result<()> {
+    match ty {
+        Type::Path(path) => {
+            let mut errors = path
+                .path
+                .segments
+                .iter()
+                .filter_map(|segment| match &segment.arguments {
+                    PathArguments::AngleBracketed(arguments) => Some(arguments),
+                    _ => None,
+                })
+                .flat_map(|arguments| arguments.args.iter())
+                .filter_map(|argument| match argument {
+                    GenericArgument::Type(ty) => check_type(ty).err(),
+                    GenericArgument::Lifetime(lifetime) => Some(Error::new_spanned(
+                        lifetime,
+                        "lifetimes are not supported in field types, use an owned type",
+                    )),
+                    _ => None,
+                });
+
+            match errors.next() {
+                Some(mut error) => {
+                    errors.for_each(|other| error.combine(other));
+
+                    Err(error)
+                }
+                None => Ok(()),
+            }
+        }
+        Type::Group(group) => check_type(&group.elem),
+        Type::Paren(paren) => check_type(&paren.elem),
+        Type::Reference(_) => Err(Error::new_spanned(
+            ty,
+            "reference fields are not supported, use an owned type such as `String` instead of `&str`",
+        )),
+        _ => Err(Error::new_spanned(
+            ty,
+            "unsupported field type, expected a type path such as `i32`, `String`, `chrono::NaiveDateTime` or `Option<String>`",
+        )),
     }
 }
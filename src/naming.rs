@@ -0,0 +1,275 @@
+//! Helpers for deriving names (table names, function names) from the names in the struct.
+
+use syn::*;
+
+/// Words whose plural is not made by a suffix.
+const IRREGULAR: &[(&str, &str)] = &[
+    ("person", "people"),
+    ("man", "men"),
+    ("woman", "women"),
+    ("child", "children"),
+    ("mouse", "mice"),
+    ("goose", "geese"),
+    ("tooth", "teeth"),
+    ("foot", "feet"),
+    ("ox", "oxen"),
+    ("leaf", "leaves"),
+    ("knife", "knives"),
+    ("life", "lives"),
+    ("wife", "wives"),
+    ("half", "halves"),
+    ("shelf", "shelves"),
+    ("wolf", "wolves"),
+    ("thief", "thieves"),
+    ("hero", "heroes"),
+    ("potato", "potatoes"),
+    ("tomato", "tomatoes"),
+    ("echo", "echoes"),
+    ("quiz", "quizzes"),
+];
+
+/// Words that are the same in singular and plural.
+const UNCOUNTABLE: &[&str] = &[
+    "data",
+    "metadata",
+    "equipment",
+    "information",
+    "news",
+    "series",
+    "species",
+    "sheep",
+    "fish",
+    "feedback",
+];
+
+/// Convert a `CamelCase` name to `snake_case`. e.g. `BlogPost` becomes `blog_post`.
+pub(crate) fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_ascii_digit());
+            let acronym_end = i > 0
+                && chars[i - 1].is_uppercase()
+                && chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+
+            if (after_lower || acronym_end) && !snake.ends_with('_') {
+                snake.push('_');
+            }
+
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+
+    snake
+}
+
+/// Convert a `snake_case` name to `CamelCase`. e.g. `created_at` becomes `CreatedAt`.
+pub(crate) fn to_camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Pluralize an english `snake_case` name. Only the last word is changed. e.g. `blog_post` becomes `blog_posts`,
+/// `category` becomes `categories`, `analysis` becomes `analyses` and `person` becomes `people`.
+pub(crate) fn pluralize(name: &str) -> String {
+    let (prefix, word) = match name.rfind('_') {
+        Some(index) => name.split_at(index + 1),
+        None => ("", name),
+    };
+
+    let plural = if UNCOUNTABLE.contains(&word) {
+        word.to_string()
+    } else if let Some((_, plural)) = IRREGULAR.iter().find(|(singular, _)| *singular == word) {
+        plural.to_string()
+    } else if let Some(stem) = word.strip_suffix("sis") {
+        format!("{}ses", stem)
+    } else if ["s", "x", "z", "ch", "sh"].iter().any(|suffix| word.ends_with(suffix)) {
+        format!("{}es", word)
+    } else if let Some(stem) = word.strip_suffix('y').filter(|stem| ends_with_consonant(stem)) {
+        format!("{}ies", stem)
+    } else {
+        format!("{}s", word)
+    };
+
+    format!("{}{}", prefix, plural)
+}
+
+/// Whether the word ends with a consonant.
+fn ends_with_consonant(word: &str) -> bool {
+    word.chars()
+        .last()
+        .is_some_and(|c| c.is_ascii_alphabetic() && !"aeiou".contains(c))
+}
+
+/// How the `get_*_by_*` and `update_*_by_*` functions are named, given with `naming = ...`.
+#[derive(Default)]
+pub(crate) enum Naming {
+    /// The field as it is. e.g. `get_body_by_id`
+    None,
+
+    /// The field in english plural. e.g. `get_bodies_by_id`
+    #[default]
+    Plural,
+
+    /// A template for the part after `get_` and `update_`, with `{field}`, `{fields}` (the plural) and `{key}`.
+    /// e.g. `"{field}_list_by_{key}"` gives `get_body_list_by_id`
+    Template(String),
+}
+
+impl Naming {
+    /// Parse the value of `naming = ...`: `none`, `plural` or a template string.
+    pub fn parse(input: parse::ParseStream) -> Result<Self> {
+        if input.peek(LitStr) {
+            let template: LitStr = input.parse()?;
+            let value = template.value();
+
+            let has_field = value.contains("{field}") || value.contains("{fields}");
+
+            if !has_field || !value.contains("{key}") {
+                return Err(Error::new_spanned(
+                    template,
+                    "the naming template must contain `{key}` and one of `{field}` or `{fields}`",
+                ));
+            }
+
+            let sample = value.replace("{fields}", "a").replace("{field}", "a").replace("{key}", "a");
+
+            if parse_str::<Ident>(&format!("get_{}", sample)).is_err() {
+                return Err(Error::new_spanned(
+                    template,
+                    "the naming template must give a valid identifier, only `{field}`, `{fields}` and `{key}` are replaced",
+                ));
+            }
+
+            return Ok(Naming::Template(value));
+        }
+
+        let name: Ident = input.parse()?;
+
+        match name.to_string().as_str() {
+            "none" => Ok(Naming::None),
+            "plural" => Ok(Naming::Plural),
+            _ => Err(Error::new_spanned(
+                name,
+                "expected `none`, `plural` or a template like `\"{field}_by_{key}\"`",
+            )),
+        }
+    }
+
+    /// The part of a function name after `get_` or `update_`, for getting `field` by `key`.
+    pub fn name(&self, field: &str, key: &str) -> String {
+        match self {
+            Naming::None => format!("{}_by_{}", field, key),
+            Naming::Plural => format!("{}_by_{}", pluralize(field), key),
+            Naming::Template(template) => template
+                .replace("{fields}", &pluralize(field))
+                .replace("{field}", field)
+                .replace("{key}", key),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pluralize_regular() {
+        let cases = [
+            ("user", "users"),
+            ("blog_post", "blog_posts"),
+            ("category", "categories"),
+            ("day", "days"),
+            ("status", "statuses"),
+            ("box", "boxes"),
+            ("church", "churches"),
+            ("wish", "wishes"),
+            ("buzz", "buzzes"),
+            ("analysis", "analyses"),
+            ("data_analysis", "data_analyses"),
+        ];
+
+        for (singular, plural) in cases {
+            assert_eq!(pluralize(singular), plural, "plural of `{}`", singular);
+        }
+    }
+
+    #[test]
+    fn pluralize_irregular_and_uncountable() {
+        let cases = [
+            ("person", "people"),
+            ("child", "children"),
+            ("leaf", "leaves"),
+            ("hero", "heroes"),
+            ("quiz", "quizzes"),
+            ("pop_quiz", "pop_quizzes"),
+            ("admin_person", "admin_people"),
+            ("news", "news"),
+            ("user_metadata", "user_metadata"),
+            ("sheep", "sheep"),
+        ];
+
+        for (singular, plural) in cases {
+            assert_eq!(pluralize(singular), plural, "plural of `{}`", singular);
+        }
+    }
+
+    #[test]
+    fn snake_case() {
+        let cases = [
+            ("User", "user"),
+            ("BlogPost", "blog_post"),
+            ("HTTPRequest", "http_request"),
+            ("UserV2", "user_v2"),
+            ("Oauth2Token", "oauth2_token"),
+            ("already_snake", "already_snake"),
+        ];
+
+        for (name, snake) in cases {
+            assert_eq!(to_snake_case(name), snake, "snake_case of `{}`", name);
+        }
+    }
+
+    #[test]
+    fn camel_case() {
+        let cases = [("id", "Id"), ("created_at", "CreatedAt"), ("user__id_", "UserId"), ("x2_y", "X2Y")];
+
+        for (name, camel) in cases {
+            assert_eq!(to_camel_case(name), camel, "CamelCase of `{}`", name);
+        }
+    }
+
+    #[test]
+    fn naming_names() {
+        let parse = |input: &str| parse::Parser::parse_str(Naming::parse, input);
+
+        assert_eq!(Naming::default().name("body", "id"), "bodies_by_id");
+        assert_eq!(parse("none").unwrap().name("body", "id"), "body_by_id");
+        assert_eq!(parse("plural").unwrap().name("status", "id"), "statuses_by_id");
+        assert_eq!(parse("\"{field}_list_by_{key}\"").unwrap().name("body", "id"), "body_list_by_id");
+        assert_eq!(parse("\"{fields}_for_{key}\"").unwrap().name("category", "slug"), "categories_for_slug");
+    }
+
+    #[test]
+    fn naming_rejects_invalid() {
+        let parse = |input: &str| parse::Parser::parse_str(Naming::parse, input);
+
+        assert!(parse("singular").is_err());
+        assert!(parse("\"{field}_list\"").is_err());
+        assert!(parse("\"by_{key}\"").is_err());
+        assert!(parse("\"{field}-by-{key}\"").is_err());
+    }
+}
@@ -0,0 +1,207 @@
+//! `*_pooled` functions, running the generated functions on a connection from a pool.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::ParseStream;
+use syn::*;
+
+/// The pool the `*_pooled` functions take, from `pool = ...`.
+pub(crate) enum Pool {
+    /// `&diesel::r2d2::Pool<diesel::r2d2::ConnectionManager<Conn>>`, the connection is checked out and used in
+    /// `tokio::task::spawn_blocking`.
+    R2d2,
+
+    /// `&deadpool_diesel::Pool<deadpool_diesel::Manager<Conn>>`, the connection is used with `interact`, which runs
+    /// it in `spawn_blocking` too.
+    Deadpool,
+}
+
+impl Pool {
+    /// Parse the value of `pool = ...`, `r2d2` or `deadpool`.
+    pub fn parse(input: ParseStream) -> Result<Self> {
+        let name: Ident = input.parse()?;
+
+        match name.to_string().as_str() {
+            "r2d2" => Ok(Pool::R2d2),
+            "deadpool" => Ok(Pool::Deadpool),
+            _ => Err(Error::new_spanned(name, "expected `r2d2` or `deadpool`")),
+        }
+    }
+
+    /// Type of the pool of `connection_type`.
+    fn ty(&self, connection_type: &Path) -> TokenStream {
+        match self {
+            Pool::R2d2 => quote! { diesel::r2d2::Pool<diesel::r2d2::ConnectionManager<#connection_type>> },
+            Pool::Deadpool => quote! { deadpool_diesel::Pool<deadpool_diesel::Manager<#connection_type>> },
+        }
+    }
+
+    /// Body running `call` with `connection` from `pool`, `call` gives a `QueryResult<#return_type>`.
+    fn body(&self, call: TokenStream, return_type: &Type) -> TokenStream {
+        match self {
+            Pool::R2d2 => quote! {
+                let pool = pool.clone();
+
+                tokio::task::spawn_blocking(move || -> Result<#return_type, Box<dyn std::error::Error + Send + Sync>> {
+                    let mut connection = pool.get()?;
+                    let connection = &mut *connection;
+
+                    Ok(#call?)
+                })
+                .await?
+            },
+            // `InteractError` holds the panic, which is not `Sync`.
+            Pool::Deadpool => quote! {
+                let connection = pool.get().await?;
+
+                let result = connection
+                    .interact(move |connection| #call)
+                    .await
+                    .map_err(|error| error.to_string())?;
+
+                Ok(result?)
+            },
+        }
+    }
+}
+
+/// A `*_pooled` counterpart of every function in the `impl` blocks of `functions`.
+///
+/// The parameters taken by reference are taken by value, so that they can be moved to the blocking task. Errors of
+/// the pool, the task and the query are returned as `Box<dyn std::error::Error + Send + Sync>`.
+pub(crate) fn pooled_functions(pool: &Pool, connection_type: &Path, functions: TokenStream) -> Result<TokenStream> {
+    let file: File = parse2(functions)?;
+    let pool_type = pool.ty(connection_type);
+
+    let mut pooled = Vec::new();
+
+    for item in file.items {
+        let item_impl = match item {
+            Item::Impl(item_impl) => item_impl,
+            _ => continue,
+        };
+
+        let self_ty = &item_impl.self_ty;
+        let mut methods = Vec::new();
+
+        for impl_item in item_impl.items {
+            let method = match impl_item {
+                ImplItem::Method(method) => method,
+                _ => continue,
+            };
+
+            let name = &method.sig.ident;
+            let fn_name = format_ident!("{}_pooled", name);
+            let doc = format!(
+                "[`{0}::{1}`] on a connection from the pool, returns once the blocking task running it is done",
+                quote!(#self_ty),
+                name
+            );
+
+            let mut params = Vec::new();
+            let mut param_types = Vec::new();
+            let mut args = Vec::new();
+
+            // the first parameter is the connection.
+            for input in method.sig.inputs.iter().skip(1) {
+                let (pat, ty) = match input {
+                    FnArg::Typed(PatType { pat, ty, .. }) => (pat, ty),
+                    FnArg::Receiver(_) => continue,
+                };
+
+                params.push(pat.clone());
+
+                match &**ty {
+                    Type::Reference(reference) => {
+                        param_types.push(reference.elem.clone());
+                        args.push(quote! { &#pat });
+                    }
+                    ty => match option_of_reference(ty) {
+                        Some(inner) => {
+                            param_types.push(Box::new(parse_quote!(Option<#inner>)));
+                            args.push(quote! { #pat.as_ref() });
+                        }
+                        None => {
+                            param_types.push(Box::new(ty.clone()));
+                            args.push(quote! { #pat });
+                        }
+                    },
+                }
+            }
+
+            let return_type = match &method.sig.output {
+                ReturnType::Type(_, ty) => query_result_inner(ty).ok_or_else(|| {
+                    Error::new_spanned(ty, "expected a function returning `diesel::result::QueryResult`")
+                })?,
+                ReturnType::Default => continue,
+            };
+
+            // the values moved to the blocking task must be `Send + 'static`.
+            let generics = &method.sig.generics;
+            let mut where_clause = generics.where_clause.clone().unwrap_or_else(|| parse_quote!(where));
+
+            for param in generics.type_params() {
+                let ident = &param.ident;
+
+                where_clause.predicates.push(parse_quote!(#ident: Send + 'static));
+            }
+
+            let body = pool.body(quote! { Self::#name(connection, #(#args),*) }, return_type);
+
+            methods.push(quote! {
+                #[doc = #doc]
+                pub async fn #fn_name #generics (pool: &#pool_type, #(#params: #param_types),*) -> Result<#return_type, Box<dyn std::error::Error + Send + Sync>> #where_clause {
+                    #body
+                }
+            });
+        }
+
+        pooled.push(quote! {
+            impl #self_ty {
+                #(#methods)*
+            }
+        });
+    }
+
+    Ok(quote! { #(#pooled)* })
+}
+
+/// `T` of `Option<&T>`, e.g. the cursor of `get_after_*`.
+fn option_of_reference(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(TypePath { qself: None, path }) => path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first()? {
+            GenericArgument::Type(Type::Reference(reference)) => Some(&reference.elem),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `T` of `diesel::result::QueryResult<T>`.
+fn query_result_inner(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(TypePath { qself: None, path }) => path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident != "QueryResult" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first()? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,120 @@
+//! Differences between the diesel versions, chosen with the `diesel1` and `diesel2` features.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::*;
+
+use crate::connection::Connection;
+
+/// Whether the generated code is for diesel 2. `diesel2` takes precedence when both features are enabled.
+pub(crate) const DIESEL2: bool = cfg!(feature = "diesel2");
+
+// without a feature the version would silently be diesel 1, e.g. with `default-features = false` and `serde`.
+#[cfg(not(any(feature = "diesel1", feature = "diesel2")))]
+compile_error!("enable the `diesel1` or the `diesel2` feature of diesel-ease, e.g. `features = [\"diesel2\"]`");
+
+/// Attribute giving the table to the derives of a generated struct.
+///
+/// diesel 1 only takes the name of the table, so the table must be in scope. diesel 2 takes its path.
+pub(crate) fn table_name_attr(table_module: &Path) -> TokenStream {
+    if DIESEL2 {
+        quote! { #[diesel(table_name = #table_module)] }
+    } else {
+        let table_name = table_module.segments.last().unwrap().ident.to_string();
+
+        quote! { #[table_name = #table_name] }
+    }
+}
+
+/// Attribute giving the primary key to the derives of a generated struct.
+pub(crate) fn primary_key_attr(primary_key: &[&Ident]) -> TokenStream {
+    if DIESEL2 {
+        quote! { #[diesel(primary_key(#(#primary_key),*))] }
+    } else {
+        quote! { #[primary_key(#(#primary_key),*)] }
+    }
+}
+
+/// Attributes added to the model so that rows can be loaded with `select(Self::as_select())`.
+///
+/// With diesel 2 the model derives `Selectable`, with its table and, for a connection type, the backend to check
+/// the fields against. Attributes that the model already has are not added again. Nothing is added with diesel 1.
+pub(crate) fn model_attrs(attrs: &[Attribute], table_module: &Path, connection: &Connection) -> Result<Vec<Attribute>> {
+    if !DIESEL2 {
+        return Ok(vec![]);
+    }
+
+    let mut added: Vec<Attribute> = Vec::new();
+
+    if !derives(attrs, "Selectable") {
+        added.push(parse_quote!(#[derive(diesel::Selectable)]));
+    }
+
+    let options = diesel_options(attrs)?;
+
+    if !options.iter().any(|(key, _)| key == "table_name") {
+        added.push(parse_quote!(#[diesel(table_name = #table_module)]));
+    }
+
+    if let Connection::Type(connection) = connection {
+        if !options.iter().any(|(key, _)| key == "check_for_backend") {
+            added.push(parse_quote!(#[diesel(check_for_backend(<#connection as diesel::Connection>::Backend))]));
+        }
+    }
+
+    Ok(added)
+}
+
+/// The options in all `#[diesel(...)]` attributes, with the tokens after their key, e.g. `= users` or `(Pg)`.
+pub(crate) fn diesel_options(attrs: &[Attribute]) -> Result<Vec<(Ident, TokenStream)>> {
+    let mut options = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("diesel")) {
+        attr.parse_args_with(|input: parse::ParseStream| {
+            while !input.is_empty() {
+                let key: Ident = input.parse()?;
+                let mut value = TokenStream::new();
+
+                while !input.is_empty() && !input.peek(Token![,]) {
+                    value.extend(std::iter::once(input.parse::<proc_macro2::TokenTree>()?));
+                }
+
+                options.push((key, value));
+
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(options)
+}
+
+/// Whether the struct derives the trait, by the last segment of its path.
+fn derives(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().filter(|attr| attr.path.is_ident("derive")).any(|attr| {
+        attr.parse_args_with(punctuated::Punctuated::<Path, Token![,]>::parse_terminated)
+            .map(|paths| paths.iter().any(|path| path.segments.last().is_some_and(|segment| segment.ident == name)))
+            .unwrap_or(false)
+    })
+}
+
+/// Run the `body` in a transaction on `connection`, the body uses `connection` too.
+pub(crate) fn transaction(body: TokenStream) -> TokenStream {
+    if DIESEL2 {
+        quote! {
+            connection.transaction::<_, diesel::result::Error, _>(|connection| {
+                #body
+            })
+        }
+    } else {
+        quote! {
+            connection.transaction::<_, diesel::result::Error, _>(|| {
+                #body
+            })
+        }
+    }
+}
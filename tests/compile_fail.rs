@@ -0,0 +1,7 @@
+//! The errors the macro reports for what it does not support, see `tests/ui`.
+
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
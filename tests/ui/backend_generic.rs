@@ -0,0 +1,9 @@
+use diesel_ease::diesel_ease;
+
+#[diesel_ease(generic, table = crate::schema::users, backend = mysql)]
+pub struct User {
+    pub id: i32,
+    pub name: String,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+#[macro_use]
+extern crate diesel;
+
+use diesel::sqlite::SqliteConnection;
+use diesel_ease::diesel_ease;
+
+table! { users (id) { id -> Integer, name -> Text, } }
+
+pub type Db = SqliteConnection;
+
+#[diesel_ease(Db, backend = pg, table = crate::users, ops(get_all))]
+#[derive(Queryable)]
+pub struct User {
+    pub id: i32,
+    pub name: String,
+}
+
+fn main() {}
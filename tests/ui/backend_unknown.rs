@@ -0,0 +1,9 @@
+use diesel_ease::diesel_ease;
+
+#[diesel_ease(PgConnection, backend = oracle)]
+pub struct User {
+    pub id: i32,
+    pub name: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use diesel_ease::diesel_ease;
+
+#[diesel_ease(PgConnection)]
+pub struct Event {
+    pub id: i32,
+    #[ease(cursor)]
+    pub ended_at: Option<i64>,
+}
+
+fn main() {}
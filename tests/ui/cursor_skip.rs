@@ -0,0 +1,10 @@
+use diesel_ease::diesel_ease;
+
+#[diesel_ease(PgConnection)]
+pub struct Event {
+    pub id: i32,
+    #[ease(skip, cursor)]
+    pub title: String,
+}
+
+fn main() {}
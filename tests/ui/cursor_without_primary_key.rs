@@ -0,0 +1,10 @@
+use diesel_ease::diesel_ease;
+
+#[diesel_ease(PgConnection)]
+pub struct Log {
+    pub line: String,
+    #[ease(cursor)]
+    pub created_at: i64,
+}
+
+fn main() {}
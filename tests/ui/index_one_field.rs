@@ -0,0 +1,10 @@
+use diesel_ease::diesel_ease;
+
+#[diesel_ease(PgConnection)]
+#[ease(index(title))]
+pub struct Post {
+    pub id: i32,
+    pub title: String,
+}
+
+fn main() {}
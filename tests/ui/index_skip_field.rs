@@ -0,0 +1,12 @@
+use diesel_ease::diesel_ease;
+
+#[diesel_ease(PgConnection)]
+#[ease(index(title, body))]
+pub struct Post {
+    pub id: i32,
+    pub title: String,
+    #[ease(skip)]
+    pub body: String,
+}
+
+fn main() {}
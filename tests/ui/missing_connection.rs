@@ -0,0 +1,9 @@
+use diesel_ease::diesel_ease;
+
+#[diesel_ease(table = crate::schema::users)]
+pub struct User {
+    pub id: i32,
+    pub name: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use diesel_ease::diesel_ease;
+
+#[diesel_ease(PgConnection)]
+pub struct User<'a> {
+    pub id: i32,
+    pub name: &'a str,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use diesel_ease::diesel_ease;
+
+#[diesel_ease(PgConnection, naming = none)]
+pub struct User {
+    pub id: i32,
+    #[ease(unique)]
+    pub email: String,
+    pub title: String,
+}
+
+fn main() {}
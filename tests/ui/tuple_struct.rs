@@ -0,0 +1,6 @@
+use diesel_ease::diesel_ease;
+
+#[diesel_ease(PgConnection)]
+pub struct User(i32, String);
+
+fn main() {}